@@ -3,21 +3,22 @@ use std::fs;
 use std::io::{self, IsTerminal, Read};
 
 use clap::{Parser, ValueEnum};
-use sysexits::ExitCode;
-
-use lib_core::Shell;
+use lib_core::{ExitStatus, Shell, ShellOption};
 use lib_repl::Prompt;
 
 #[derive(Parser, Debug)]
 #[command(version = env!("VERSION"))]
-#[command(after_help = ShellOption::display_possible_options())]
+#[command(after_help = display_possible_options())]
 #[command(about, long_about = None)]
-#[group(multiple = false)]
 struct Cli {
     /// Execute script at path
     #[arg(group = "input")]
     path: Option<String>,
 
+    /// Arguments passed to the script
+    #[arg(requires = "path", trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+
     /// Run command non-interactively
     #[arg(short, long, group = "input")]
     command: Option<String>,
@@ -51,25 +52,17 @@ struct Cli {
     unset: Vec<ShellOption>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-enum ShellOption {
-    /// Setting shell options not yet implemented
-    None,
-}
-
-impl ShellOption {
-    fn display_possible_options() -> String {
-        let options = Self::value_variants()
-            .iter()
-            .filter_map(|v| v.to_possible_value())
-            .map(|v| format!("- {}: {}", v.get_name(), v.get_help().unwrap_or_default()));
-
-        ["Settable shell options:".to_string()]
-            .into_iter()
-            .chain(options)
-            .collect::<Vec<_>>()
-            .join("\n  ")
-    }
+fn display_possible_options() -> String {
+    let options = ShellOption::value_variants()
+        .iter()
+        .filter_map(|v| v.to_possible_value())
+        .map(|v| format!("- {}: {}", v.get_name(), v.get_help().unwrap_or_default()));
+
+    ["Settable shell options:".to_string()]
+        .into_iter()
+        .chain(options)
+        .collect::<Vec<_>>()
+        .join("\n  ")
 }
 
 #[derive(Clone, PartialEq)]
@@ -95,23 +88,36 @@ impl Cli {
 }
 
 impl From<Cli> for Shell {
-    fn from(_cli: Cli) -> Self {
-        Self::default()
+    fn from(cli: Cli) -> Self {
+        let mut shell = Self::default();
+        let name = cli.path.or_else(|| env::args().next()).unwrap_or_default();
+        shell.set_args([name].into_iter().chain(cli.args).collect());
+
+        for option in cli.set {
+            shell.set_option(option, true);
+        }
+
+        for option in cli.unset {
+            shell.set_option(option, false);
+        }
+
+        shell
     }
 }
 
-fn main() -> ExitCode {
+fn main() -> ExitStatus {
     let mut cli = Cli::parse();
 
-    if !io::stdin().is_terminal() {
+    if cli.path.is_none() && cli.command.is_none() && !io::stdin().is_terminal() {
         cli.stdin = true;
     }
 
     let mode = cli.parse_shell_mode();
     let mut shell = Shell::from(cli);
 
-    match mode {
+    let code = match mode {
         ShellMode::Interactive => {
+            shell.set_interactive(true);
             let history_source = shell.config_filepath(".crsh-history");
             let mut prompt = Prompt::new(&mut shell).with_history(history_source);
 
@@ -119,7 +125,7 @@ fn main() -> ExitCode {
                 Ok(code) => code,
                 Err(e) => {
                     eprintln!("crsh: prompt error: {e:#?}");
-                    ExitCode::OsErr
+                    ExitStatus::FAILURE
                 }
             }
         }
@@ -130,7 +136,7 @@ fn main() -> ExitCode {
                 Ok(_) => shell.interpret(&input),
                 Err(e) => {
                     eprintln!("crsh: failed to read stdin: {e}");
-                    ExitCode::IoErr
+                    ExitStatus::FAILURE
                 }
             }
         }
@@ -139,8 +145,12 @@ fn main() -> ExitCode {
             Ok(script) => shell.interpret(&script),
             Err(e) => {
                 eprintln!("crsh: failed to run script at \"{path}\": {e}");
-                ExitCode::NoInput
+                ExitStatus::NOT_FOUND
             }
         },
-    }
+    };
+
+    shell.set_exit_code(code);
+    shell.run_exit_trap();
+    shell.exit_code()
 }
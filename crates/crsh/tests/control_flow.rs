@@ -0,0 +1,67 @@
+use std::process::Command;
+
+/// Runs `script` with `crsh -c`, returning its standard output.
+fn stdout(script: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_crsh"))
+        .args(["-c", script])
+        .output()
+        .expect("failed to run crsh");
+
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn for_loop_runs_over_words() {
+    assert_eq!(stdout("for x in a b c; do echo $x; done"), "a\nb\nc\n");
+    assert_eq!(stdout("set -- p q; for x; do echo $x; done"), "p\nq\n");
+}
+
+#[test]
+fn while_and_until_loops_test_each_iteration() {
+    let script = "declare -i i=0\nwhile test $i -lt 3\ndo\n  echo $i\n  i=i+1\ndone";
+    assert_eq!(stdout(script), "0\n1\n2\n");
+
+    let script = "declare -i i=0; until test $i -eq 2; do echo $i; i=i+1; done";
+    assert_eq!(stdout(script), "0\n1\n");
+}
+
+#[test]
+fn break_and_continue_unwind_nested_loops() {
+    let script = "for i in 1 2 3; do test $i = 2 && continue; test $i = 3 && break; echo $i; done";
+    assert_eq!(stdout(script), "1\n");
+
+    let script = "for i in 1 2; do for j in a b; do test $j = b && continue 2; echo $i$j; done; \
+                  echo skipped; done";
+    assert_eq!(stdout(script), "1a\n2a\n");
+
+    let script = "while true; do while true; do break 2; done; echo skipped; done; echo $?";
+    assert_eq!(stdout(script), "0\n");
+}
+
+#[test]
+fn loop_status_is_that_of_its_last_command() {
+    assert_eq!(stdout("for x in 1; do false; done; echo $?"), "1\n");
+    assert_eq!(stdout("for x in; do false; done; echo $?"), "0\n");
+}
+
+#[test]
+fn functions_take_positional_parameters() {
+    let script = "set -- outer; f() { echo $1 $2; }; f a b; echo $1";
+    assert_eq!(stdout(script), "a b\nouter\n");
+
+    let script = "function g {\n  echo $1\n}\ng x | cat";
+    assert_eq!(stdout(script), "x\n");
+}
+
+#[test]
+fn locals_are_dynamically_scoped() {
+    let script = "x=global; f() { local x=f; g; }; g() { echo $x; }; f; echo $x";
+    assert_eq!(stdout(script), "f\nglobal\n");
+}
+
+#[test]
+fn return_leaves_the_function() {
+    let script = "f() { for i in 1 2; do test $i = 2 && return 7; echo $i; done; echo skipped; }; \
+                  f; echo $?";
+    assert_eq!(stdout(script), "1\n7\n");
+}
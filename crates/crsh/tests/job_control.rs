@@ -0,0 +1,114 @@
+#![cfg(target_os = "linux")]
+
+use std::{
+    ffi::CString,
+    fs,
+    io::{Read, Write},
+    os::{fd::AsFd, unix::ffi::OsStrExt, unix::process::CommandExt},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use nix::{
+    fcntl::{OFlag, open},
+    pty::{Winsize, openpty},
+    sys::{signal::Signal, stat::Mode},
+    unistd::{setsid, ttyname},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Types `line` into an interactive crsh running on a new pseudo terminal, so that job control
+/// is enabled, and waits for the shell to exit.
+fn interactive(home: &PathBuf, line: &str) {
+    let size = Winsize {
+        ws_row: 24,
+        ws_col: 80,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+
+    let pty = openpty(Some(&size), None).expect("failed to open a pty");
+    let tty = ttyname(pty.slave.as_fd()).expect("failed to name the pty");
+    let tty = CString::new(tty.as_os_str().as_bytes()).unwrap();
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_crsh"));
+    command
+        .env("HOME", home)
+        .stdin(Stdio::from(pty.slave.try_clone().unwrap()))
+        .stdout(Stdio::from(pty.slave.try_clone().unwrap()))
+        .stderr(Stdio::from(pty.slave));
+
+    // start a new session with the pty as its controlling terminal
+    unsafe {
+        command.pre_exec(move || {
+            setsid()?;
+            open(tty.as_c_str(), OFlag::O_RDWR, Mode::empty())?;
+            Ok(())
+        });
+    }
+
+    let mut child = command.spawn().expect("failed to start crsh");
+    drop(command);
+
+    let mut master = fs::File::from(pty.master);
+    let mut reader = master.try_clone().unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    // drain the terminal so the shell never blocks on output, noting when the prompt appears
+    thread::spawn(move || {
+        let mut buffer = [0; 1024];
+
+        while let Ok(1..) = reader.read(&mut buffer) {
+            _ = sender.send(());
+        }
+    });
+
+    receiver.recv_timeout(TIMEOUT).expect("no prompt");
+    master.write_all(format!("{line}\r").as_bytes()).unwrap();
+
+    let start = Instant::now();
+
+    while child.try_wait().unwrap().is_none() {
+        if start.elapsed() > TIMEOUT {
+            _ = child.kill();
+            panic!("`{line}` did not finish within {TIMEOUT:?}");
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn substitution_child_takes_interrupts() {
+    let home = std::env::temp_dir().join(format!("crsh-job-control-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    let output = home.join("sigign");
+
+    interactive(
+        &home,
+        &format!(
+            "x=$(grep SigIgn /proc/self/status); echo $x > {}; exit",
+            output.display()
+        ),
+    );
+
+    let status = fs::read_to_string(&output).expect("no output");
+    _ = fs::remove_dir_all(&home);
+
+    let mask = status.trim().trim_start_matches("SigIgn:").trim();
+    let mask = u64::from_str_radix(mask, 16).expect("bad mask");
+    let bit = |signal: Signal| 1 << (signal as u64 - 1);
+
+    assert_eq!(
+        mask & (bit(Signal::SIGINT) | bit(Signal::SIGQUIT)),
+        0,
+        "{status}"
+    );
+
+    let stops = bit(Signal::SIGTSTP) | bit(Signal::SIGTTIN) | bit(Signal::SIGTTOU);
+    assert_eq!(mask & stops, stops, "{status}");
+}
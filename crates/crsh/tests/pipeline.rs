@@ -0,0 +1,64 @@
+use std::{
+    io::Read,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs `script` with `crsh -c`, returning its standard output, or panicking if it does not finish
+/// within the timeout.
+fn run(script: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_crsh"))
+        .args(["-c", script])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start crsh");
+
+    let start = Instant::now();
+
+    while child.try_wait().expect("failed to wait for crsh").is_none() {
+        if start.elapsed() > TIMEOUT {
+            _ = child.kill();
+            panic!("`{script}` did not finish within {TIMEOUT:?}");
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    let mut output = String::new();
+    child
+        .stdout
+        .take()
+        .expect("stdout is piped")
+        .read_to_string(&mut output)
+        .expect("failed to read output");
+
+    output
+}
+
+#[test]
+fn subshell_stage_sees_closed_pipe() {
+    assert_eq!(run("(yes) | head -n 1"), "y\n");
+}
+
+#[test]
+fn builtin_stage_sees_closed_pipe() {
+    assert_eq!(run("printf '%0200000d' 0 | head -c 3"), "000");
+}
+
+#[test]
+fn background_pipeline_lists_every_pid() {
+    let output = run("sleep 0.2 | sleep 0.2 | sleep 0.2 & jobs -l");
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 3, "{output}");
+    assert!(lines[0].starts_with("[1]+ "), "{output}");
+    assert!(
+        lines[1..]
+            .iter()
+            .all(|line| line.trim().parse::<u32>().is_ok())
+    );
+}
@@ -0,0 +1,53 @@
+use std::process::{Command, Output};
+
+/// Runs `script` with `crsh -c`.
+fn run(script: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_crsh"))
+        .args(["-c", script])
+        .output()
+        .expect("failed to run crsh")
+}
+
+fn stdout(script: &str) -> String {
+    String::from_utf8_lossy(&run(script).stdout).into_owned()
+}
+
+#[test]
+fn quoted_assignment_keeps_whitespace() {
+    assert_eq!(stdout("a='hello world'; echo $a"), "hello world\n");
+    assert_eq!(stdout("x=hi; a=\"$x  there\"; echo \"$a\""), "hi  there\n");
+}
+
+#[test]
+fn quoted_assignment_keeps_operators() {
+    assert_eq!(stdout("b='x;y'; echo $b"), "x;y\n");
+    assert_eq!(stdout("d='1 && 2'; echo $d"), "1 && 2\n");
+    assert_eq!(stdout("declare -i n='1 + 2'; echo $n"), "3\n");
+}
+
+#[test]
+fn quoted_sections_join_a_word() {
+    assert_eq!(stdout("echo a'b c'\"d\"e"), "ab cde\n");
+}
+
+#[test]
+fn unterminated_quote_is_a_parse_error() {
+    let output = run("a='x; echo ran");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn quoted_word_continues_unquoted() {
+    assert_eq!(stdout("echo 'a'b"), "ab\n");
+    assert_eq!(stdout("echo \"a\"b"), "ab\n");
+    assert_eq!(stdout("y='b'c; echo \"$y\""), "bc\n");
+}
+
+#[test]
+fn parameters_join_quoted_text() {
+    assert_eq!(stdout("x=hi; printf '<%s>' \"a$x\""), "<ahi>");
+    assert_eq!(stdout("x=hi; printf '<%s>' \"${x}y\""), "<hiy>");
+    assert_eq!(stdout("x=hi; y=\"a$x\"; printf '<%s>' \"$y\""), "<ahi>");
+}
@@ -0,0 +1,30 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Runs `script` with `crsh -c`, feeding `input` to its standard input.
+fn run(script: &str, input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_crsh"))
+        .args(["-c", script])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start crsh");
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin.write_all(input.as_bytes()).unwrap();
+    drop(stdin);
+
+    let output = child.wait_with_output().expect("failed to wait for crsh");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn count_is_in_characters() {
+    assert_eq!(run("read -n 2 a; echo $a", "h\u{e9}llo\n"), "h\u{e9}\n");
+    assert_eq!(
+        run("read -n 2 a; echo $a", "\u{20ac}\u{20ac}\u{20ac}\n"),
+        "\u{20ac}\u{20ac}\n"
+    );
+}
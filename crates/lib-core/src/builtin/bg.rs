@@ -0,0 +1,67 @@
+use clap::Parser;
+use lib_os::{io, process};
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::JobState};
+
+/// Resume jobs in the background
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Jobs to resume in the background
+    jobspec: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn bg(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["bg"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if !process::job_control() {
+            io.eprintln("bg: no job control");
+            return ExitStatus::FAILURE;
+        }
+
+        let specs = if cli.jobspec.is_empty() {
+            vec![None]
+        } else {
+            cli.jobspec.iter().map(|spec| Some(spec.as_str())).collect()
+        };
+
+        let mut exit_code = ExitStatus::SUCCESS;
+
+        for spec in specs {
+            let id = match shell.jobs.find(spec) {
+                Ok(id) => id,
+                Err(e) => {
+                    io.eprintln(format!("bg: {e}"));
+                    exit_code = ExitStatus::FAILURE;
+                    continue;
+                }
+            };
+
+            let Some(job) = shell.jobs.get_mut(id) else {
+                continue;
+            };
+
+            if job.state() != JobState::Stopped {
+                io.eprintln(format!("bg: job {id} already in background"));
+                continue;
+            }
+
+            if let Err(e) = job.resume() {
+                io.eprintln(format!("bg: failed to resume job: {e}"));
+                exit_code = ExitStatus::FAILURE;
+                continue;
+            }
+
+            let command = job.command.clone();
+            io.println(format!("[{id}]{} {command} &", shell.jobs.marker(id)));
+        }
+
+        exit_code
+    }
+}
@@ -0,0 +1,105 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::Flow};
+
+/// Exit from loops
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Number of enclosing loops to exit
+    count: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn r#break(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["break"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        loop_control(shell, io, "break", cli.count.as_deref(), Flow::Break)
+    }
+}
+
+/// Raises `flow` to leave or restart the Nth enclosing loop, clamped to the current loop depth.
+pub(super) fn loop_control(
+    shell: &mut Shell,
+    io: &mut io::Context,
+    name: &str,
+    count: Option<&str>,
+    flow: fn(usize) -> Flow,
+) -> ExitStatus {
+    if shell.loop_depth == 0 {
+        io.eprintln(format!(
+            "{name}: only meaningful in a `for', `while', or `until' loop"
+        ));
+        return ExitStatus::SUCCESS;
+    }
+
+    let count = match count {
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                io.eprintln(format!("{name}: {arg}: loop count out of range"));
+                return ExitStatus::FAILURE;
+            }
+        },
+        None => 1,
+    };
+
+    shell.flow = Some(flow(count.min(shell.loop_depth)));
+    ExitStatus::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(loop_depth: usize, count: Option<&str>) -> (ExitStatus, Option<Flow>) {
+        let mut shell = Shell {
+            loop_depth,
+            ..Default::default()
+        };
+
+        let code = loop_control(
+            &mut shell,
+            &mut io::Context::_null(),
+            "break",
+            count,
+            Flow::Break,
+        );
+
+        (code, shell.flow)
+    }
+
+    #[test]
+    fn count_defaults_to_one() {
+        assert_eq!(run(2, None), (ExitStatus::SUCCESS, Some(Flow::Break(1))));
+    }
+
+    #[test]
+    fn count_is_clamped_to_loop_depth() {
+        assert_eq!(
+            run(2, Some("2")),
+            (ExitStatus::SUCCESS, Some(Flow::Break(2)))
+        );
+        assert_eq!(
+            run(2, Some("5")),
+            (ExitStatus::SUCCESS, Some(Flow::Break(2)))
+        );
+    }
+
+    #[test]
+    fn invalid_count_is_rejected() {
+        assert_eq!(run(1, Some("0")), (ExitStatus::FAILURE, None));
+        assert_eq!(run(1, Some("x")), (ExitStatus::FAILURE, None));
+    }
+
+    #[test]
+    fn outside_loop_does_nothing() {
+        assert_eq!(run(0, Some("1")), (ExitStatus::SUCCESS, None));
+    }
+}
@@ -1,73 +1,92 @@
-use std::{env, path::Path};
+use std::path::{Component, Path};
 
 use clap::Parser;
-use sysexits::ExitCode;
+use lib_os::io;
 
-use lib_os::{dir, io};
-
-use crate::{Shell, builtin::Builtin};
+use crate::{ExitStatus, Shell, builtin::Builtin};
 
+/// Change the current directory
 #[derive(Parser)]
-#[group(multiple = false)]
-struct Cli {
+pub(super) struct Cli {
     /// Pathname of the new working directory
     directory: Option<String>,
 
     /// Handle the operand dot-dot logically
-    #[arg(short = 'L', group = "dotdot")]
+    #[arg(short = 'L', overrides_with = "physical")]
     logical: bool,
 
     /// Handle the operand dot-dot physically
-    #[arg(short = 'P', group = "dotdot")]
+    #[arg(short = 'P', overrides_with = "logical")]
     physical: bool,
 }
 
 impl Builtin {
-    pub(super) fn cd(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitCode {
+    pub(super) fn cd(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
         let cli = match Cli::try_parse_from(["cd"].iter().chain(args)) {
             Ok(cli) => cli,
             Err(e) => {
                 shell.io.eprintln(e.to_string());
-                return ExitCode::Usage;
+                return ExitStatus::MISUSE;
             }
         };
 
-        if cli.logical || cli.physical {
-            // https://pubs.opengroup.org/onlinepubs/9699919799/utilities/cd.html
-            shell.io.eprintln("-L and -P options not yet implemented");
-            return ExitCode::Usage;
-        }
-
-        let path = if let Some(dir) = &cli.directory {
-            if dir == "-" {
-                shell.old_pwd.clone()
-            } else {
-                dir.to_string()
+        let (path, print) = match cli.directory.as_deref() {
+            Some("-") if shell.old_pwd.is_empty() => {
+                io.eprintln("cd: OLDPWD not set");
+                return ExitStatus::FAILURE;
             }
-        } else {
-            dir::my_home()
+            Some("-") => (shell.old_pwd.clone(), true),
+            Some(dir) => search_cdpath(shell, dir),
+            None => (shell.home(), false),
         };
 
-        if !Path::new(&path).is_dir() {
-            io.eprintln(format!(
-                "cd: cannot access '{path}': No such file or directory"
-            ));
-            return ExitCode::NoInput;
+        let code = change_dir(shell, io, "cd", &path, cli.physical);
+
+        if print && code.is_success() {
+            io.println(&shell.pwd);
         }
 
-        if let Err(e) = env::set_current_dir(&path) {
-            io.eprintln(format!("cd: cannot access '{path}': {e}"));
-            ExitCode::NoInput
-        } else {
-            shell.old_pwd = shell.pwd.clone();
-            shell.pwd = dir::current();
+        code
+    }
+}
 
-            unsafe {
-                env::set_var("OLDPWD", &shell.old_pwd);
-                env::set_var("PWD", &shell.pwd);
-            }
+pub(super) fn change_dir(
+    shell: &mut Shell,
+    io: &mut io::Context,
+    name: &str,
+    path: &str,
+    physical: bool,
+) -> ExitStatus {
+    if !Path::new(path).is_dir() {
+        io.eprintln(format!(
+            "{name}: cannot access '{path}': No such file or directory"
+        ));
+        return ExitStatus::FAILURE;
+    }
+
+    if let Err(e) = shell.change_dir(path, physical) {
+        io.eprintln(format!("{name}: cannot access '{path}': {e}"));
+        ExitStatus::FAILURE
+    } else {
+        ExitStatus::SUCCESS
+    }
+}
 
-            ExitCode::Ok
+fn search_cdpath(shell: &Shell, dir: &str) -> (String, bool) {
+    let relative = matches!(
+        Path::new(dir).components().next(),
+        Some(Component::Normal(_))
+    );
+
+    if relative && let Some(cdpath) = shell.var("CDPATH") {
+        for entry in cdpath.split(':') {
+            let path = Path::new(if entry.is_empty() { "." } else { entry }).join(dir);
+
+            if path.is_dir() {
+                return (path.to_string_lossy().to_string(), !entry.is_empty());
+            }
         }
     }
+
+    (dir.to_string(), false)
 }
@@ -0,0 +1,19 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Do nothing, successfully
+#[derive(Parser)]
+#[command(disable_help_flag = true)]
+pub(super) struct Cli {
+    /// Arguments, which are ignored
+    #[arg(allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn colon(_shell: &mut Shell, _io: &mut io::Context, _args: &[&str]) -> ExitStatus {
+        ExitStatus::SUCCESS
+    }
+}
@@ -0,0 +1,100 @@
+use clap::Parser;
+use lib_os::{dir, io};
+
+use crate::{
+    ExitStatus, Shell,
+    builtin::{Builtin, r#type},
+    error::Error,
+};
+
+/// Run a command, or display information about it
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Command to run, bypassing any shell functions
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    command: Vec<String>,
+
+    /// Search for the command using a default path that finds the standard utilities
+    #[arg(short = 'p')]
+    default_path: bool,
+
+    /// Print the pathname or word the shell would use to invoke each command
+    #[arg(short = 'v', conflicts_with = "verbose")]
+    brief: bool,
+
+    /// Print a description of how the shell would interpret each command
+    #[arg(short = 'V')]
+    verbose: bool,
+}
+
+impl Builtin {
+    pub(super) fn command(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["command"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.brief || cli.verbose {
+            let mut code = ExitStatus::SUCCESS;
+
+            for name in &cli.command {
+                let kind = r#type::resolve(shell, name, false).into_iter().next();
+
+                match kind {
+                    Some(kind) if cli.verbose => io.println(kind.describe(name)),
+                    Some(kind) => match kind.path() {
+                        Some(path) => io.println(path.display().to_string()),
+                        None => io.println(name),
+                    },
+                    None => {
+                        if cli.verbose {
+                            io.eprintln(format!("command: {name}: not found"));
+                        }
+
+                        code = ExitStatus::FAILURE;
+                    }
+                }
+            }
+
+            return code;
+        }
+
+        let Some((name, args)) = cli.command.split_first() else {
+            return ExitStatus::SUCCESS;
+        };
+
+        let name = if cli.default_path && !name.contains('/') && Builtin::get(name).is_none() {
+            match dir::search_path(dir::DEFAULT_PATH.as_ref(), name).next() {
+                Some(path) => path.to_string_lossy().to_string(),
+                None => {
+                    io.eprintln(format!("command: {name}: not found"));
+                    return ExitStatus::NOT_FOUND;
+                }
+            }
+        } else {
+            name.clone()
+        };
+
+        let ctx = match io.try_clone() {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                io.eprintln(format!("command: {e}"));
+                return ExitStatus::FAILURE;
+            }
+        };
+
+        let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>();
+
+        match shell.run_command(ctx, &name, &args) {
+            Ok(code) => code,
+            Err(Error::Flow(flow)) => {
+                shell.flow = Some(flow);
+                flow.code()
+            }
+            Err(e) => shell.report(e),
+        }
+    }
+}
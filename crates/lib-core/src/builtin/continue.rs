@@ -0,0 +1,25 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::Flow};
+
+/// Resume the next iteration of loops
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Number of enclosing loops to skip to the end of
+    count: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn r#continue(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["continue"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        super::r#break::loop_control(shell, io, "continue", cli.count.as_deref(), Flow::Continue)
+    }
+}
@@ -0,0 +1,218 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{
+    ExitStatus, Shell,
+    builtin::Builtin,
+    parsing,
+    shell::{Attributes, Value},
+};
+
+/// Set variable values and attributes
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Variables to declare, each optionally assigned a value
+    #[arg(value_name = "NAME[=VALUE]")]
+    names: Vec<String>,
+
+    /// Make each NAME an indexed array
+    #[arg(short = 'a')]
+    indexed: bool,
+
+    /// Make each NAME an associative array
+    #[arg(short = 'A', conflicts_with = "indexed")]
+    associative: bool,
+
+    /// Evaluate values assigned to each NAME arithmetically
+    #[arg(short = 'i')]
+    integer: bool,
+
+    /// Convert values assigned to each NAME to lower case
+    #[arg(short = 'l', overrides_with = "uppercase")]
+    lowercase: bool,
+
+    /// Convert values assigned to each NAME to upper case
+    #[arg(short = 'u', overrides_with = "lowercase")]
+    uppercase: bool,
+
+    /// Make each NAME a reference to the variable named by its value
+    #[arg(short = 'n')]
+    nameref: bool,
+
+    /// Make each NAME readonly
+    #[arg(short = 'r')]
+    readonly: bool,
+
+    /// Export each NAME to the environment of commands
+    #[arg(short = 'x')]
+    exported: bool,
+
+    /// Declare global variables when used in a function
+    #[arg(short = 'g')]
+    global: bool,
+
+    /// Print the attributes and value of each NAME
+    #[arg(short = 'p')]
+    print: bool,
+
+    /// Restrict to functions, printing their definitions
+    #[arg(short = 'f')]
+    functions: bool,
+
+    /// Restrict to functions, printing only their names
+    #[arg(short = 'F')]
+    function_names: bool,
+}
+
+impl Builtin {
+    pub(super) fn declare(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        declare(shell, io, "declare", args)
+    }
+}
+
+/// Runs `declare` or `local`, the latter always declaring in the current function scope.
+pub(super) fn declare(
+    shell: &mut Shell,
+    io: &mut io::Context,
+    builtin: &str,
+    args: &[&str],
+) -> ExitStatus {
+    let cli = match Cli::try_parse_from([builtin].iter().chain(args)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            io.eprintln(e.to_string());
+            return ExitStatus::MISUSE;
+        }
+    };
+
+    let local = builtin == "local" || (shell.variables.is_local_scope() && !cli.global);
+
+    let attributes = Attributes {
+        exported: cli.exported,
+        readonly: cli.readonly,
+        integer: cli.integer,
+        lowercase: cli.lowercase,
+        uppercase: cli.uppercase,
+        nameref: cli.nameref,
+    };
+
+    if cli.functions || cli.function_names {
+        // there are no shell functions, so none can be listed or found
+        return match cli.names.is_empty() {
+            true => ExitStatus::SUCCESS,
+            false => ExitStatus::FAILURE,
+        };
+    }
+
+    if cli.names.is_empty() {
+        list(shell, io, builtin, &cli, attributes);
+        return ExitStatus::SUCCESS;
+    }
+
+    if cli.print {
+        let mut code = ExitStatus::SUCCESS;
+
+        for name in &cli.names {
+            match shell.variables.lookup(name) {
+                Some(variable) => io.println(variable.declaration(name)),
+                None => {
+                    io.eprintln(format!("{builtin}: {name}: not found"));
+                    code = ExitStatus::FAILURE;
+                }
+            }
+        }
+
+        return code;
+    }
+
+    let mut code = ExitStatus::SUCCESS;
+
+    for arg in &cli.names {
+        let (name, subscript, append, value) = match arg.contains('=') {
+            true => match parsing::Parser::split_assignment(arg) {
+                Some((name, subscript, append, value)) => (name, subscript, append, Some(value)),
+                None => ("", None, false, None),
+            },
+            false => (arg.as_str(), None, false, None),
+        };
+
+        if !parsing::Parser::check_name(name) {
+            io.eprintln(format!("{builtin}: `{arg}': not a valid identifier"));
+            code = ExitStatus::FAILURE;
+            continue;
+        }
+
+        if (cli.indexed || cli.associative)
+            && let Err(e) = shell.variables.declare_array(name, cli.associative, local)
+        {
+            io.eprintln(format!("{builtin}: {name}: {e}"));
+            code = ExitStatus::FAILURE;
+            continue;
+        }
+
+        let array = subscript.is_none()
+            && value.is_some_and(|value| value.starts_with('(') && value.ends_with(')'));
+
+        let scalar = value.filter(|_| subscript.is_none() && !append && !array);
+        let value = value.filter(|_| scalar.is_none());
+        let scalar = scalar.map(|value| Value::Scalar(value.to_string()));
+
+        if let Err(e) = shell.variables.declare(name, scalar, attributes, local) {
+            io.eprintln(format!("{builtin}: {name}: {e}"));
+            code = ExitStatus::FAILURE;
+            continue;
+        }
+
+        let Some(value) = value else {
+            continue;
+        };
+
+        let assigned = if array {
+            shell.run_nested(arg).is_success()
+        } else {
+            let value = match append {
+                true => shell.appended(name, subscript, value),
+                false => value.to_string(),
+            };
+
+            match subscript {
+                Some(key) => shell.set_element(name, key, &value),
+                None => shell.set_var(name, &value),
+            }
+        };
+
+        if !assigned {
+            code = ExitStatus::FAILURE;
+        }
+    }
+
+    code
+}
+
+/// Lists variables, in `declare` form if printing or filtering by attribute.
+fn list(shell: &Shell, io: &mut io::Context, builtin: &str, cli: &Cli, attributes: Attributes) {
+    let filtered = attributes != Attributes::default() || cli.indexed || cli.associative;
+
+    let variables = match builtin {
+        "local" => shell.variables.locals(),
+        _ => shell.variables.iter(),
+    };
+
+    for (name, variable) in variables {
+        let kind = match variable.value {
+            Some(Value::Indexed(_)) => cli.indexed || !cli.associative,
+            Some(Value::Associative(_)) => cli.associative || !cli.indexed,
+            _ => !cli.indexed && !cli.associative,
+        };
+
+        if !kind || !variable.attributes.contains(attributes) {
+            continue;
+        }
+
+        if cli.print || filtered {
+            io.println(variable.declaration(name));
+        } else if let Some(value) = &variable.value {
+            io.println(value.assignment(name));
+        }
+    }
+}
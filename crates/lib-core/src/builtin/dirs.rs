@@ -0,0 +1,107 @@
+use clap::Parser;
+use lib_os::{dir, io};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Display the directory stack
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Display only the Nth entry, counting from the top with +N or the bottom with -N
+    #[arg(allow_hyphen_values = true)]
+    index: Option<String>,
+
+    /// Clear the directory stack
+    #[arg(short = 'c')]
+    clear: bool,
+
+    /// Display full paths rather than abbreviating the home directory to `~`
+    #[arg(short = 'l')]
+    long: bool,
+
+    /// Display one entry per line
+    #[arg(short = 'p')]
+    lines: bool,
+
+    /// Display one entry per line, prefixed with its position in the stack
+    #[arg(short = 'v')]
+    verbose: bool,
+}
+
+impl Builtin {
+    pub(super) fn dirs(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["dirs"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.clear {
+            shell.dirs.clear();
+            return ExitStatus::SUCCESS;
+        }
+
+        if let Some(spec) = &cli.index {
+            let stack = shell.directory_stack();
+
+            return match parse_index(shell, spec) {
+                Ok(i) => {
+                    io.println(display(&stack[i], cli.long));
+                    ExitStatus::SUCCESS
+                }
+                Err(e) => {
+                    io.eprintln(format!("dirs: {e}"));
+                    ExitStatus::FAILURE
+                }
+            };
+        }
+
+        if cli.verbose {
+            for (i, entry) in shell.directory_stack().iter().enumerate() {
+                io.println(format!("{i:>2}  {}", display(entry, cli.long)));
+            }
+        } else if cli.lines {
+            for entry in shell.directory_stack() {
+                io.println(display(&entry, cli.long));
+            }
+        } else {
+            print_stack(shell, io, cli.long);
+        }
+
+        ExitStatus::SUCCESS
+    }
+}
+
+pub(super) fn print_stack(shell: &Shell, io: &mut io::Context, long: bool) {
+    let line = shell
+        .directory_stack()
+        .iter()
+        .map(|entry| display(entry, long))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    io.println(line);
+}
+
+/// Parses a `+N` or `-N` argument into an index into the directory stack.
+pub(super) fn parse_index(shell: &Shell, spec: &str) -> Result<usize, String> {
+    if !spec.starts_with(['+', '-']) {
+        return Err(format!("{spec}: invalid argument"));
+    }
+
+    shell
+        .stack_index(spec)
+        .ok_or_else(|| format!("{spec}: directory stack index out of range"))
+}
+
+fn display(entry: &str, long: bool) -> String {
+    let home = dir::my_home();
+
+    match entry.strip_prefix(&home) {
+        Some(rest) if !long && !home.is_empty() && (rest.is_empty() || rest.starts_with('/')) => {
+            format!("~{rest}")
+        }
+        _ => entry.to_string(),
+    }
+}
@@ -0,0 +1,86 @@
+use clap::{ArgAction, Parser};
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::JobState};
+
+/// Remove jobs from the job table
+#[derive(Parser)]
+#[command(disable_help_flag = true)]
+pub(super) struct Cli {
+    /// Jobs to remove from the job table
+    jobspec: Vec<String>,
+
+    /// Remove all jobs if no job specifications are given
+    #[arg(short = 'a')]
+    all: bool,
+
+    /// Remove only running jobs
+    #[arg(short = 'r')]
+    running: bool,
+
+    /// Keep jobs in the table but do not send them SIGHUP when the shell exits
+    #[arg(short = 'h')]
+    nohup: bool,
+
+    /// Print help
+    #[arg(long, action = ArgAction::Help)]
+    help: Option<bool>,
+}
+
+impl Builtin {
+    pub(super) fn disown(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["disown"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let mut exit_code = ExitStatus::SUCCESS;
+
+        let ids = if !cli.jobspec.is_empty() {
+            let mut ids = Vec::new();
+
+            for spec in &cli.jobspec {
+                match shell.jobs.find(Some(spec)) {
+                    Ok(id) => ids.push(id),
+                    Err(e) => {
+                        io.eprintln(format!("disown: {e}"));
+                        exit_code = ExitStatus::FAILURE;
+                    }
+                }
+            }
+
+            ids
+        } else if cli.all || cli.running {
+            shell.jobs.ids()
+        } else {
+            match shell.jobs.find(None) {
+                Ok(id) => vec![id],
+                Err(e) => {
+                    io.eprintln(format!("disown: {e}"));
+                    return ExitStatus::FAILURE;
+                }
+            }
+        };
+
+        for id in ids {
+            let Some(job) = shell.jobs.get_mut(id) else {
+                continue;
+            };
+
+            if cli.running && job.state() != JobState::Running {
+                continue;
+            }
+
+            if cli.nohup {
+                job.nohup = true;
+            } else {
+                shell.jobs.remove(id);
+            }
+        }
+
+        exit_code
+    }
+}
@@ -0,0 +1,166 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+// Only describes the options, as echo prints any argument it does not recognise literally
+/// Write arguments to standard output
+#[derive(Parser)]
+#[command(disable_help_flag = true)]
+pub(super) struct Cli {
+    /// Strings to print, separated by spaces
+    #[arg(allow_hyphen_values = true)]
+    strings: Vec<String>,
+
+    /// Do not print a trailing newline
+    #[arg(short = 'n')]
+    no_newline: bool,
+
+    /// Interpret backslash escapes
+    #[arg(short = 'e')]
+    escapes: bool,
+
+    /// Do not interpret backslash escapes
+    #[arg(short = 'E')]
+    no_escapes: bool,
+}
+
+impl Builtin {
+    pub(super) fn echo(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let mut newline = true;
+        let mut escapes = false;
+        let mut args = args;
+
+        while let Some(flags) = args.first().and_then(|arg| arg.strip_prefix('-'))
+            && !flags.is_empty()
+            && flags.chars().all(|c| "neE".contains(c))
+        {
+            for flag in flags.chars() {
+                match flag {
+                    'n' => newline = false,
+                    'e' => escapes = true,
+                    _ => escapes = false,
+                }
+            }
+
+            args = &args[1..];
+        }
+
+        let mut output = Vec::new();
+
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                output.push(b' ');
+            }
+
+            if escapes {
+                let (text, stop) = unescape(arg, false);
+                output.extend(text);
+
+                if stop {
+                    _ = io.write(1, output);
+                    return ExitStatus::SUCCESS;
+                }
+            } else {
+                output.extend(arg.as_bytes());
+            }
+        }
+
+        if newline {
+            output.push(b'\n');
+        }
+
+        _ = io.write(1, output);
+        ExitStatus::SUCCESS
+    }
+}
+
+/// Expands backslash escapes, returning true alongside the text if output should stop at `\c`.
+/// Format strings take octal as `\nnn` and keep `\c` literally, other strings take `\0nnn`.
+/// Octal and `\x` escapes give raw bytes, which need not be valid UTF-8.
+pub(super) fn unescape(input: &str, format: bool) -> (Vec<u8>, bool) {
+    fn take_digits(
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+        radix: u32,
+        max: usize,
+        initial: u32,
+    ) -> (u32, usize) {
+        let mut value = initial;
+        let mut count = 0;
+
+        while count < max
+            && let Some(digit) = chars.peek().and_then(|c| c.to_digit(radix))
+        {
+            value = value * radix + digit;
+            count += 1;
+            chars.next();
+        }
+
+        (value, count)
+    }
+
+    fn push(output: &mut Vec<u8>, c: char) {
+        output.extend(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    let mut output = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            push(&mut output, c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some('a') => '\x07',
+            Some('b') => '\x08',
+            Some('c') if !format => return (output, true),
+            Some('e') | Some('E') => '\x1b',
+            Some('f') => '\x0c',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('v') => '\x0b',
+            Some('\\') => '\\',
+            Some('0') if !format => {
+                let (value, _) = take_digits(&mut chars, 8, 3, 0);
+                output.push(value as u8);
+                continue;
+            }
+            Some(c @ '0'..='7') if format => {
+                let (value, _) = take_digits(&mut chars, 8, 2, c as u32 - '0' as u32);
+                output.push(value as u8);
+                continue;
+            }
+            Some(c @ ('x' | 'u' | 'U')) => {
+                let max = match c {
+                    'x' => 2,
+                    'u' => 4,
+                    _ => 8,
+                };
+
+                match take_digits(&mut chars, 16, max, 0) {
+                    (_, 0) => {
+                        output.push(b'\\');
+                        c
+                    }
+                    (value, _) if c == 'x' => {
+                        output.push(value as u8);
+                        continue;
+                    }
+                    (value, _) => char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER),
+                }
+            }
+            Some(c) => {
+                output.push(b'\\');
+                c
+            }
+            None => '\\',
+        };
+
+        push(&mut output, escaped);
+    }
+
+    (output, false)
+}
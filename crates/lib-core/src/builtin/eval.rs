@@ -0,0 +1,26 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Run arguments as a shell command
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Arguments to join and execute as a command
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn eval(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["eval"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        shell.run_nested(&cli.args.join(" "))
+    }
+}
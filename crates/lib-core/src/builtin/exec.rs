@@ -0,0 +1,102 @@
+use std::{io::ErrorKind, os::unix::process::CommandExt, path::PathBuf};
+
+use clap::Parser;
+use lib_os::{io, process};
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::Flow};
+
+/// Replace the shell with a command
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Command to replace the shell with, if absent the redirections apply to the shell
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    command: Vec<String>,
+
+    /// Pass NAME as the zeroth argument of the command
+    #[arg(short = 'a', value_name = "NAME")]
+    name: Option<String>,
+
+    /// Execute the command with an empty environment
+    #[arg(short = 'c')]
+    clear: bool,
+
+    /// Place a dash at the beginning of the zeroth argument
+    #[arg(short = 'l')]
+    login: bool,
+}
+
+impl Builtin {
+    pub(super) fn exec(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["exec"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let Some((name, args)) = cli.command.split_first() else {
+            return match io.try_clone() {
+                Ok(ctx) => {
+                    shell.io = ctx;
+                    ExitStatus::SUCCESS
+                }
+                Err(e) => {
+                    io.eprintln(format!("exec: {e}"));
+                    ExitStatus::FAILURE
+                }
+            };
+        };
+
+        let path = if name.contains('/') {
+            PathBuf::from(name)
+        } else if let Some(path) = shell.find_command(name) {
+            path
+        } else {
+            io.eprintln(format!("exec: {name}: not found"));
+
+            if !shell.interactive {
+                shell.flow = Some(Flow::Exit(ExitStatus::NOT_FOUND));
+            }
+
+            return ExitStatus::NOT_FOUND;
+        };
+
+        let mut arg0 = cli.name.unwrap_or_else(|| name.clone());
+
+        if cli.login {
+            arg0.insert(0, '-');
+        }
+
+        let mut cmd = std::process::Command::new(path);
+        cmd.arg0(arg0).args(args);
+
+        cmd.env_clear();
+
+        if !cli.clear {
+            cmd.envs(shell.variables.environment());
+        }
+
+        if let Err(e) = io.attach(&mut cmd) {
+            io.eprintln(format!("exec: {e}"));
+            return ExitStatus::FAILURE;
+        }
+
+        shell.io.flush();
+        io.flush();
+        let e = process::exec(&mut cmd);
+
+        let code = match e.kind() {
+            ErrorKind::NotFound => ExitStatus::NOT_FOUND,
+            _ => ExitStatus::NOT_EXECUTABLE,
+        };
+
+        io.eprintln(format!("exec: {name}: {e}"));
+
+        if !shell.interactive {
+            shell.flow = Some(Flow::Exit(code));
+        }
+
+        code
+    }
+}
@@ -1,20 +1,38 @@
-use sysexits::ExitCode;
-
+use clap::Parser;
 use lib_os::io;
 
-use crate::{builtin::Builtin, Shell};
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::Flow};
+
+/// Exit the shell
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Exit status, defaulting to that of the last command
+    #[arg(allow_negative_numbers = true)]
+    status: Option<String>,
+}
 
 impl Builtin {
-    pub(super) fn exit(shell: &mut Shell, _io: &mut io::Context, args: &[&str]) -> ExitCode {
-        shell.should_exit = true;
+    pub(super) fn exit(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["exit"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let code = match cli.status {
+            Some(arg) => match arg.parse::<i64>() {
+                Ok(code) => ExitStatus::new(code.rem_euclid(256) as u8),
+                Err(_) => {
+                    io.eprintln(format!("exit: {arg}: numeric argument required"));
+                    ExitStatus::MISUSE
+                }
+            },
+            None => shell.exit_code,
+        };
 
-        args.first()
-            .map(|arg| {
-                arg.parse::<i32>()
-                    .unwrap_or(0)
-                    .try_into()
-                    .unwrap_or(ExitCode::Ok)
-            })
-            .unwrap_or(ExitCode::Ok)
+        shell.flow = Some(Flow::Exit(code));
+        code
     }
 }
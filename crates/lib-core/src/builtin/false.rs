@@ -0,0 +1,19 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Return an unsuccessful status
+#[derive(Parser)]
+#[command(disable_help_flag = true)]
+pub(super) struct Cli {
+    /// Arguments, which are ignored
+    #[arg(allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn r#false(_shell: &mut Shell, _io: &mut io::Context, _args: &[&str]) -> ExitStatus {
+        ExitStatus::FAILURE
+    }
+}
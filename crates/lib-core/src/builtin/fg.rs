@@ -0,0 +1,54 @@
+use clap::Parser;
+use lib_os::{io, process};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Move a job to the foreground
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Job to resume in the foreground
+    jobspec: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn fg(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["fg"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if !process::job_control() {
+            io.eprintln("fg: no job control");
+            return ExitStatus::FAILURE;
+        }
+
+        let job = match shell.jobs.find(cli.jobspec.as_deref()) {
+            Ok(id) => shell.jobs.remove(id),
+            Err(e) => {
+                io.eprintln(format!("fg: {e}"));
+                return ExitStatus::FAILURE;
+            }
+        };
+
+        let Some(mut job) = job else {
+            return ExitStatus::FAILURE;
+        };
+
+        io.println(&job.command);
+
+        if let Err(e) = job.resume() {
+            io.eprintln(format!("fg: failed to resume job: {e}"));
+        }
+
+        match shell.foreground(job) {
+            Ok(code) => code,
+            Err(e) => {
+                io.eprintln(format!("fg: {e}"));
+                ExitStatus::FAILURE
+            }
+        }
+    }
+}
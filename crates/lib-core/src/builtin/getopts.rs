@@ -0,0 +1,198 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, parsing};
+
+/// Parse option arguments
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Option characters to recognise, those followed by `:` take an argument
+    #[arg(allow_hyphen_values = true)]
+    optstring: String,
+
+    /// Variable to store the next option character in
+    name: String,
+
+    /// Arguments to parse instead of the positional parameters
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn getopts(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["getopts"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if !parsing::Parser::check_name(&cli.name) {
+            io.eprintln(format!("getopts: `{}': not a valid identifier", cli.name));
+            return ExitStatus::FAILURE;
+        }
+
+        let args = if cli.args.is_empty() {
+            shell.args.iter().skip(1).cloned().collect()
+        } else {
+            cli.args
+        };
+
+        let mut index = shell
+            .var("OPTIND")
+            .and_then(|optind| optind.parse::<usize>().ok())
+            .unwrap_or(1)
+            .max(1);
+
+        let mut offset = match shell.getopts_position {
+            (last, offset) if last == index => offset,
+            _ => 0,
+        };
+
+        let silent = cli.optstring.starts_with(':');
+        let optstring = cli.optstring.trim_start_matches(':');
+
+        let Some(arg) = args
+            .get(index - 1)
+            .map(|arg| arg.chars().collect::<Vec<_>>())
+        else {
+            return finish(shell, &cli.name, index);
+        };
+
+        if offset == 0 {
+            if arg == ['-', '-'] {
+                return finish(shell, &cli.name, index + 1);
+            } else if arg.len() < 2 || arg[0] != '-' {
+                return finish(shell, &cli.name, index);
+            }
+
+            offset = 1;
+        }
+
+        let Some(&option) = arg.get(offset) else {
+            return finish(shell, &cli.name, index + 1);
+        };
+
+        offset += 1;
+        let mut optarg = None;
+
+        let result = match optstring.find(option).filter(|_| option != ':') {
+            Some(i) if optstring[i + option.len_utf8()..].starts_with(':') => {
+                if offset < arg.len() {
+                    optarg = Some(arg[offset..].iter().collect());
+                    offset = arg.len();
+                    option
+                } else if let Some(next) = args.get(index) {
+                    optarg = Some(next.clone());
+                    index += 1;
+                    option
+                } else if silent {
+                    optarg = Some(option.to_string());
+                    ':'
+                } else {
+                    io.eprintln(format!("getopts: option requires an argument -- {option}"));
+                    '?'
+                }
+            }
+            Some(_) => option,
+            None if silent => {
+                optarg = Some(option.to_string());
+                '?'
+            }
+            None => {
+                io.eprintln(format!("getopts: illegal option -- {option}"));
+                '?'
+            }
+        };
+
+        if offset >= arg.len() {
+            offset = 0;
+            index += 1;
+        }
+
+        match optarg {
+            Some(optarg) => _ = shell.set_var("OPTARG", &optarg),
+            None => _ = shell.variables.unset("OPTARG"),
+        }
+
+        shell.getopts_position = (index, offset);
+        shell.set_var("OPTIND", &index.to_string());
+        shell.set_var(&cli.name, &result.to_string());
+        ExitStatus::SUCCESS
+    }
+}
+
+fn finish(shell: &mut Shell, name: &str, index: usize) -> ExitStatus {
+    shell.getopts_position = (index, 0);
+    _ = shell.variables.unset("OPTARG");
+    shell.set_var("OPTIND", &index.to_string());
+    shell.set_var(name, "?");
+    ExitStatus::FAILURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `getopts` once, returning its status and the resulting option, OPTIND and OPTARG.
+    fn next(shell: &mut Shell, args: &[&str]) -> (ExitStatus, String, String, Option<String>) {
+        let code = Builtin::getopts(shell, &mut io::Context::_null(), args);
+        let option = shell.var("opt").unwrap_or_default();
+        let optind = shell.var("OPTIND").unwrap_or_default();
+        (code, option, optind, shell.var("OPTARG"))
+    }
+
+    #[test]
+    fn options_and_arguments_advance_optind() {
+        let mut shell = Shell::default();
+        let args = ["ab:c", "opt", "-a", "-bvalue", "-b", "next", "-ca", "file"];
+
+        let steps = [
+            ("a", "2", None),
+            ("b", "3", Some("value")),
+            ("b", "5", Some("next")),
+            ("c", "5", None),
+            ("a", "6", None),
+        ];
+
+        for (option, optind, optarg) in steps {
+            let (code, opt, ind, arg) = next(&mut shell, &args);
+
+            assert_eq!(code, ExitStatus::SUCCESS);
+            assert_eq!((opt.as_str(), ind.as_str()), (option, optind));
+            assert_eq!(arg.as_deref(), optarg);
+        }
+
+        let (code, opt, ind, arg) = next(&mut shell, &args);
+
+        assert_eq!(code, ExitStatus::FAILURE);
+        assert_eq!((opt.as_str(), ind.as_str(), arg), ("?", "6", None));
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let mut shell = Shell::default();
+        let args = ["a", "opt", "-a", "--", "-a"];
+
+        assert_eq!(next(&mut shell, &args).0, ExitStatus::SUCCESS);
+
+        let (code, opt, ind, _) = next(&mut shell, &args);
+        assert_eq!(
+            (code, opt.as_str(), ind.as_str()),
+            (ExitStatus::FAILURE, "?", "3")
+        );
+    }
+
+    #[test]
+    fn silent_mode_reports_errors_in_optarg() {
+        let mut shell = Shell::default();
+        let args = [":ab:", "opt", "-x", "-b"];
+
+        let (_, opt, _, arg) = next(&mut shell, &args);
+        assert_eq!((opt.as_str(), arg.as_deref()), ("?", Some("x")));
+
+        let (_, opt, _, arg) = next(&mut shell, &args);
+        assert_eq!((opt.as_str(), arg.as_deref()), (":", Some("b")));
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Remember or display command locations
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Commands to look up on PATH and remember
+    names: Vec<String>,
+
+    /// Forget all remembered locations
+    #[arg(short = 'r')]
+    reset: bool,
+
+    /// Forget the remembered location of each NAME
+    #[arg(short = 'd', conflicts_with_all = ["print", "path"])]
+    delete: bool,
+
+    /// Print the remembered location of each NAME
+    #[arg(short = 't', conflicts_with = "path")]
+    print: bool,
+
+    /// Use PATHNAME as the location of NAME
+    #[arg(short = 'p', value_name = "PATHNAME")]
+    path: Option<PathBuf>,
+
+    /// Display the table in a format that may be reused as input
+    #[arg(short = 'l')]
+    list: bool,
+}
+
+impl Builtin {
+    pub(super) fn hash(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["hash"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        shell.commands.validate(shell.var("PATH"));
+
+        if cli.reset {
+            shell.commands.clear();
+        }
+
+        if cli.names.is_empty() {
+            if cli.reset {
+                return ExitStatus::SUCCESS;
+            } else if shell.commands.is_empty() {
+                io.println("hash: hash table empty");
+            } else if cli.list {
+                for (name, hashed) in shell.commands.iter() {
+                    io.println(format!("builtin hash -p {} {name}", hashed.path.display()));
+                }
+            } else {
+                io.println("hits\tcommand");
+
+                for (_, hashed) in shell.commands.iter() {
+                    io.println(format!("{:>4}\t{}", hashed.hits, hashed.path.display()));
+                }
+            }
+
+            return ExitStatus::SUCCESS;
+        }
+
+        let mut code = ExitStatus::SUCCESS;
+
+        for name in &cli.names {
+            let found = if cli.delete {
+                shell.commands.remove(name)
+            } else if cli.print {
+                match shell.commands.get(name) {
+                    Some(hashed) if cli.names.len() > 1 => {
+                        io.println(format!("{name}\t{}", hashed.path.display()));
+                        true
+                    }
+                    Some(hashed) => {
+                        io.println(hashed.path.display().to_string());
+                        true
+                    }
+                    None => false,
+                }
+            } else if let Some(path) = &cli.path {
+                shell.commands.insert(name, path.clone(), 0);
+                true
+            } else if name.contains('/') || Builtin::get(name).is_some() {
+                true
+            } else if let Some(path) = shell.commands.search(name) {
+                shell.commands.insert(name, path, 0);
+                true
+            } else {
+                false
+            };
+
+            if !found {
+                io.eprintln(format!("hash: {name}: not found"));
+                code = ExitStatus::FAILURE;
+            }
+        }
+
+        code
+    }
+}
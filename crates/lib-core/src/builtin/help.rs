@@ -0,0 +1,186 @@
+use clap::{Arg, ArgAction, Parser};
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Display information about builtin commands
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Builtins to describe, matching every name that starts with a pattern
+    patterns: Vec<String>,
+
+    /// Print only a short description of each builtin
+    #[arg(short = 'd', conflicts_with = "synopsis")]
+    description: bool,
+
+    /// Print only the usage of each builtin
+    #[arg(short = 's')]
+    synopsis: bool,
+}
+
+fn is_help(arg: &Arg) -> bool {
+    matches!(
+        arg.get_action(),
+        ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+    )
+}
+
+/// The argument as written in a usage line, ie. `-s SIGSPEC` or `NAMES...`.
+fn label(arg: &Arg) -> String {
+    let value = arg
+        .get_value_names()
+        .and_then(|names| names.first())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| arg.get_id().as_str().to_uppercase());
+
+    let multiple = matches!(arg.get_action(), ArgAction::Append) && arg.is_positional();
+
+    match (arg.get_short(), arg.get_long()) {
+        _ if arg.is_positional() && multiple => format!("{value}..."),
+        _ if arg.is_positional() => value,
+        (Some(short), _) if arg.get_action().takes_values() => format!("-{short} {value}"),
+        (Some(short), _) => format!("-{short}"),
+        (None, Some(long)) if arg.get_action().takes_values() => format!("--{long} {value}"),
+        (None, Some(long)) => format!("--{long}"),
+        (None, None) => value,
+    }
+}
+
+/// Options before positional arguments, leaving out help flags.
+fn arguments(command: &clap::Command) -> impl Iterator<Item = &Arg> {
+    let options = command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set() && !is_help(arg));
+
+    options.chain(command.get_positionals())
+}
+
+impl Builtin {
+    /// A one line usage synopsis, ie. `kill [-s SIGSPEC] [-n SIGNUM] [-l] [TARGETS...]`.
+    pub fn usage(&self) -> String {
+        let command = self.definition();
+        let mut usage = self.name().to_string();
+
+        for arg in arguments(&command) {
+            match arg.is_required_set() {
+                true => usage.push_str(&format!(" {}", label(arg))),
+                false => usage.push_str(&format!(" [{}]", label(arg))),
+            }
+        }
+
+        usage
+    }
+
+    /// A short description of what the builtin does.
+    pub fn about(&self) -> String {
+        self.definition()
+            .get_about()
+            .map(|about| about.to_string())
+            .unwrap_or_default()
+    }
+
+    /// The full help text shown by `help NAME` and `NAME --help`.
+    pub fn help_text(&self) -> String {
+        let command = self.definition();
+        let mut help = format!("{}: {}\n", self.name(), self.usage());
+
+        let description = command.get_long_about().or(command.get_about());
+
+        if let Some(description) = description {
+            for line in description.to_string().lines() {
+                help.push_str(&format!("    {line}\n").replace("    \n", "\n"));
+            }
+        }
+
+        let (options, positionals): (Vec<_>, Vec<_>) =
+            arguments(&command).partition(|arg| !arg.is_positional());
+
+        let width = options
+            .iter()
+            .chain(&positionals)
+            .map(|arg| label(arg).len())
+            .max()
+            .unwrap_or_default();
+
+        for (heading, args) in [("Options", options), ("Arguments", positionals)] {
+            if args.is_empty() {
+                continue;
+            }
+
+            help.push_str(&format!("\n    {heading}:\n"));
+
+            for arg in args {
+                let text = arg
+                    .get_help()
+                    .map(|help| help.to_string())
+                    .unwrap_or_default();
+                help.push_str(&format!("      {:<width$}  {text}\n", label(arg)));
+            }
+        }
+
+        help
+    }
+
+    /// Whether `--help` as the first argument shows help rather than being passed to the builtin.
+    pub(super) fn has_help_flag(&self) -> bool {
+        let command = self.definition();
+
+        !command.is_disable_help_flag_set()
+            || command
+                .get_arguments()
+                .any(|arg| arg.get_long() == Some("help"))
+    }
+
+    pub(super) fn help(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["help"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.patterns.is_empty() {
+            io.println("Shell builtin commands. Type `help NAME' to find out more about NAME.\n");
+
+            let mut builtins = Builtin::all().to_vec();
+            builtins.sort_by_key(|builtin| builtin.name());
+
+            for builtin in builtins {
+                io.println(format!("{:<10} {}", builtin.name(), builtin.about()));
+            }
+
+            return ExitStatus::SUCCESS;
+        }
+
+        let mut code = ExitStatus::SUCCESS;
+
+        for pattern in &cli.patterns {
+            let matches = match Builtin::get(pattern) {
+                Some(builtin) => vec![builtin],
+                None => Builtin::all()
+                    .iter()
+                    .filter(|builtin| builtin.name().starts_with(pattern.as_str()))
+                    .copied()
+                    .collect(),
+            };
+
+            if matches.is_empty() {
+                io.eprintln(format!("help: no help topics match `{pattern}'"));
+                code = ExitStatus::FAILURE;
+            }
+
+            for builtin in matches {
+                if cli.synopsis {
+                    io.println(format!("{}: {}", builtin.name(), builtin.usage()));
+                } else if cli.description {
+                    io.println(format!("{} - {}", builtin.name(), builtin.about()));
+                } else {
+                    io.print(builtin.help_text());
+                }
+            }
+        }
+
+        code
+    }
+}
@@ -0,0 +1,107 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::JobState};
+
+/// Display the status of jobs
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Job specifications to report on
+    jobspec: Vec<String>,
+
+    /// Report process IDs in addition to the normal information
+    #[arg(short = 'l')]
+    long: bool,
+
+    /// Report only process IDs
+    #[arg(short = 'p', conflicts_with = "long")]
+    pids: bool,
+
+    /// Report only running jobs
+    #[arg(short = 'r', conflicts_with = "stopped")]
+    running: bool,
+
+    /// Report only stopped jobs
+    #[arg(short = 's')]
+    stopped: bool,
+}
+
+impl Builtin {
+    pub(super) fn jobs(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["jobs"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let ids = if cli.jobspec.is_empty() {
+            shell.jobs.ids()
+        } else {
+            let mut ids = Vec::new();
+
+            for spec in &cli.jobspec {
+                match shell.jobs.find(Some(spec)) {
+                    Ok(id) => ids.push(id),
+                    Err(e) => {
+                        io.eprintln(format!("jobs: {e}"));
+                        return ExitStatus::FAILURE;
+                    }
+                }
+            }
+
+            ids
+        };
+
+        for id in ids {
+            let Some(job) = shell.jobs.get_mut(id) else {
+                continue;
+            };
+
+            _ = job.poll();
+            let state = job.state();
+
+            if (cli.running && state != JobState::Running)
+                || (cli.stopped && state != JobState::Stopped)
+            {
+                continue;
+            }
+
+            job.notified = true;
+
+            if cli.pids {
+                for p in &job.processes {
+                    io.println(p.pid.to_string());
+                }
+            } else if cli.long {
+                let marker = shell.jobs.marker(id);
+
+                if let Some(job) = shell.jobs.get(id) {
+                    let prefix = format!("[{}]{} ", job.id, marker);
+                    let mut pids = job.processes.iter().map(|p| p.pid);
+
+                    io.println(format!(
+                        "{prefix}{} {:<24}{}",
+                        pids.next().unwrap_or_default(),
+                        job.state_description(),
+                        job.command,
+                    ));
+
+                    // the other processes of a pipeline, aligned under the first
+                    for pid in pids {
+                        io.println(format!("{:width$}{pid}", "", width = prefix.len()));
+                    }
+                }
+            } else if let Some(line) = shell.jobs.display(id) {
+                io.println(line);
+            }
+
+            if state == JobState::Done {
+                shell.jobs.remove(id);
+            }
+        }
+
+        ExitStatus::SUCCESS
+    }
+}
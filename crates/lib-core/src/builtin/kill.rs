@@ -0,0 +1,200 @@
+use clap::Parser;
+use lib_os::{io, process::Pid, signal};
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::JobState};
+
+/// Send a signal to jobs or processes
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Process IDs, negative process group IDs or job specifications to signal
+    #[arg(allow_negative_numbers = true)]
+    targets: Vec<String>,
+
+    /// Name of the signal to send
+    #[arg(short = 's', value_name = "SIGSPEC", conflicts_with = "number")]
+    signal: Option<String>,
+
+    /// Number of the signal to send
+    #[arg(short = 'n', value_name = "SIGNUM")]
+    number: Option<String>,
+
+    /// List signal names, or translate signal numbers and exit statuses to names and names to
+    /// numbers
+    #[arg(short = 'l', short_alias = 'L', conflicts_with_all = ["signal", "number"])]
+    list: bool,
+}
+
+const SIGTERM: i32 = 15;
+const SIGHUP: i32 = 1;
+
+/// Like `signal::number`, but also accepting 0 to check that a process exists.
+fn parse_signal(spec: &str) -> Option<i32> {
+    match spec {
+        "0" => Some(0),
+        _ => signal::number(spec),
+    }
+}
+
+fn list(io: &mut io::Context, specs: &[String]) -> ExitStatus {
+    if specs.is_empty() {
+        for (number, name) in signal::list() {
+            io.println(format!("{number:>2}) SIG{name}"));
+        }
+
+        return ExitStatus::SUCCESS;
+    }
+
+    let mut code = ExitStatus::SUCCESS;
+
+    for spec in specs {
+        let translated = match spec.parse::<i32>() {
+            Ok(status) if status > 128 => signal::name(status - 128).map(str::to_string),
+            Ok(number) => signal::name(number).map(str::to_string),
+            Err(_) => signal::number(spec).map(|number| number.to_string()),
+        };
+
+        match translated {
+            Some(translated) => io.println(translated),
+            None => {
+                io.eprintln(format!("kill: {spec}: invalid signal specification"));
+                code = ExitStatus::FAILURE;
+            }
+        }
+    }
+
+    code
+}
+
+impl Builtin {
+    pub(super) fn kill(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        // `-NUM` and `-NAME` give the signal directly, which clap cannot express. Any other
+        // leading option is one too, unless it is `-l`, `-s` or `-n` (with an attached value)
+        let (sig, args) = match args.split_first() {
+            Some((first, rest))
+                if let Some(spec) = first.strip_prefix('-')
+                    && !spec.is_empty()
+                    && !spec.starts_with('-') =>
+            {
+                match parse_signal(spec) {
+                    Some(sig) => (Some(sig), rest),
+                    None if spec.starts_with(['l', 'L', 's', 'n']) => (None, args),
+                    None => {
+                        io.eprintln(format!("kill: {spec}: invalid signal specification"));
+                        return ExitStatus::FAILURE;
+                    }
+                }
+            }
+            _ => (None, args),
+        };
+
+        let cli = match Cli::try_parse_from(["kill"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.list {
+            return list(io, &cli.targets);
+        }
+
+        let sig = match cli.signal.as_deref().or(cli.number.as_deref()) {
+            Some(spec) if sig.is_none() => match parse_signal(spec) {
+                Some(sig) => sig,
+                None => {
+                    io.eprintln(format!("kill: {spec}: invalid signal specification"));
+                    return ExitStatus::FAILURE;
+                }
+            },
+            _ => sig.unwrap_or(SIGTERM),
+        };
+
+        if cli.targets.is_empty() {
+            io.eprintln(
+                "kill: usage: kill [-s sigspec | -n signum | -sigspec] pid | jobspec ... or kill -l [sigspec]",
+            );
+            return ExitStatus::MISUSE;
+        }
+
+        let mut code = ExitStatus::SUCCESS;
+
+        for target in &cli.targets {
+            let result = if target.starts_with('%') {
+                let job = match shell.jobs.find(Some(target)) {
+                    Ok(id) => shell.jobs.get(id),
+                    Err(e) => {
+                        io.eprintln(format!("kill: {e}"));
+                        code = ExitStatus::FAILURE;
+                        continue;
+                    }
+                };
+
+                let Some(job) = job else {
+                    continue;
+                };
+
+                // A stopped job would only see the signal once continued
+                let resume = matches!(sig, SIGTERM | SIGHUP) && job.state() == JobState::Stopped;
+
+                let pids: Vec<Pid> = match job.pgid {
+                    Some(pgid) => vec![-pgid],
+                    None => job.processes.iter().map(|p| p.pid).collect(),
+                };
+
+                pids.into_iter().try_for_each(|pid| {
+                    signal::send(pid, sig)?;
+
+                    match signal::number("CONT") {
+                        Some(cont) if resume => signal::send(pid, cont),
+                        _ => Ok(()),
+                    }
+                })
+            } else if let Ok(pid) = target.parse::<Pid>() {
+                signal::send(pid, sig)
+            } else {
+                io.eprintln(format!(
+                    "kill: {target}: arguments must be process or job IDs"
+                ));
+                code = ExitStatus::FAILURE;
+                continue;
+            };
+
+            if let Err(e) = result {
+                io.eprintln(format!("kill: ({target}) - {e}"));
+                code = ExitStatus::FAILURE;
+            }
+        }
+
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kill(args: &[&str]) -> ExitStatus {
+        Builtin::kill(&mut Shell::default(), &mut io::Context::_null(), args)
+    }
+
+    #[test]
+    fn unknown_leading_spec_is_invalid() {
+        assert_eq!(kill(&["-99", "1"]), ExitStatus::FAILURE);
+        assert_eq!(kill(&["-FOO", "1"]), ExitStatus::FAILURE);
+        assert_eq!(kill(&["-sFOO", "1"]), ExitStatus::FAILURE);
+    }
+
+    #[test]
+    fn options_are_not_signal_specs() {
+        assert_eq!(kill(&["-l", "9"]), ExitStatus::SUCCESS);
+        assert_eq!(
+            kill(&["-s", "0", &std::process::id().to_string()]),
+            ExitStatus::SUCCESS
+        );
+        assert_eq!(
+            kill(&["-0", &std::process::id().to_string()]),
+            ExitStatus::SUCCESS
+        );
+    }
+}
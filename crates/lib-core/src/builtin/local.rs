@@ -0,0 +1,14 @@
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+impl Builtin {
+    pub(super) fn local(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        if !shell.variables.is_local_scope() {
+            io.eprintln("local: can only be used in a function");
+            return ExitStatus::FAILURE;
+        }
+
+        super::declare::declare(shell, io, "local", args)
+    }
+}
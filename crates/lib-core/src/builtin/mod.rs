@@ -1,36 +1,293 @@
-use sysexits::ExitCode;
-
+use clap::{CommandFactory, ValueEnum};
 use lib_os::io;
 
-use crate::Shell;
+use crate::{ExitStatus, Shell};
 
+mod bg;
+mod r#break;
 mod cd;
+mod colon;
+mod command;
+mod r#continue;
+mod declare;
+mod dirs;
+mod disown;
+mod echo;
+mod eval;
+mod exec;
 mod exit;
+mod r#false;
+mod fg;
+mod getopts;
+mod hash;
+mod help;
+mod jobs;
+mod kill;
+mod local;
+mod popd;
+mod printf;
+mod pushd;
+mod pwd;
+mod read;
+mod r#return;
+mod set;
+mod shift;
+mod shopt;
+mod source;
+mod test;
+mod times;
+mod trap;
+mod r#true;
+mod r#type;
+mod ulimit;
+mod umask;
+mod unset;
 mod which;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Builtin {
+    Bg,
+    Bracket,
+    Break,
     Cd,
+    Colon,
+    Command,
+    Continue,
+    Declare,
+    Dirs,
+    Disown,
+    Echo,
+    Eval,
+    Exec,
     Exit,
+    False,
+    Fg,
+    Getopts,
+    Hash,
+    Help,
+    Jobs,
+    Kill,
+    Local,
+    Popd,
+    Printf,
+    Pushd,
+    Pwd,
+    Read,
+    Return,
+    Set,
+    Shift,
+    Shopt,
+    Source,
+    Test,
+    Times,
+    Trap,
+    True,
+    Type,
+    Ulimit,
+    Umask,
+    Unset,
     Which,
 }
 
 impl Builtin {
     pub fn get(keyword: &str) -> Option<Self> {
         match keyword {
+            "." | "source" => Some(Self::Source),
+            ":" => Some(Self::Colon),
+            "[" => Some(Self::Bracket),
+            "bg" => Some(Self::Bg),
+            "break" => Some(Self::Break),
             "cd" => Some(Self::Cd),
+            "command" => Some(Self::Command),
+            "continue" => Some(Self::Continue),
+            "declare" | "typeset" => Some(Self::Declare),
+            "dirs" => Some(Self::Dirs),
+            "disown" => Some(Self::Disown),
+            "echo" => Some(Self::Echo),
+            "eval" => Some(Self::Eval),
+            "exec" => Some(Self::Exec),
             "exit" => Some(Self::Exit),
+            "false" => Some(Self::False),
+            "fg" => Some(Self::Fg),
+            "getopts" => Some(Self::Getopts),
+            "hash" => Some(Self::Hash),
+            "help" => Some(Self::Help),
+            "jobs" => Some(Self::Jobs),
+            "kill" => Some(Self::Kill),
+            "local" => Some(Self::Local),
+            "popd" => Some(Self::Popd),
+            "printf" => Some(Self::Printf),
+            "pushd" => Some(Self::Pushd),
+            "pwd" => Some(Self::Pwd),
+            "read" => Some(Self::Read),
+            "return" => Some(Self::Return),
+            "set" => Some(Self::Set),
+            "shift" => Some(Self::Shift),
+            "shopt" => Some(Self::Shopt),
+            "test" => Some(Self::Test),
+            "times" => Some(Self::Times),
+            "trap" => Some(Self::Trap),
+            "true" => Some(Self::True),
+            "type" => Some(Self::Type),
+            "ulimit" => Some(Self::Ulimit),
+            "umask" => Some(Self::Umask),
+            "unset" => Some(Self::Unset),
             "which" => Some(Self::Which),
             _ => None,
         }
     }
 
-    pub fn run(&self, shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitCode {
+    /// Every builtin, for listing and completion.
+    pub fn all() -> &'static [Self] {
+        Self::value_variants()
+    }
+
+    /// The name the builtin is documented under, ignoring aliases such as `.` and `typeset`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bg => "bg",
+            Self::Bracket => "[",
+            Self::Break => "break",
+            Self::Cd => "cd",
+            Self::Colon => ":",
+            Self::Command => "command",
+            Self::Continue => "continue",
+            Self::Declare => "declare",
+            Self::Dirs => "dirs",
+            Self::Disown => "disown",
+            Self::Echo => "echo",
+            Self::Eval => "eval",
+            Self::Exec => "exec",
+            Self::Exit => "exit",
+            Self::False => "false",
+            Self::Fg => "fg",
+            Self::Getopts => "getopts",
+            Self::Hash => "hash",
+            Self::Help => "help",
+            Self::Jobs => "jobs",
+            Self::Kill => "kill",
+            Self::Local => "local",
+            Self::Popd => "popd",
+            Self::Printf => "printf",
+            Self::Pushd => "pushd",
+            Self::Pwd => "pwd",
+            Self::Read => "read",
+            Self::Return => "return",
+            Self::Set => "set",
+            Self::Shift => "shift",
+            Self::Shopt => "shopt",
+            Self::Source => "source",
+            Self::Test => "test",
+            Self::Times => "times",
+            Self::Trap => "trap",
+            Self::True => "true",
+            Self::Type => "type",
+            Self::Ulimit => "ulimit",
+            Self::Umask => "umask",
+            Self::Unset => "unset",
+            Self::Which => "which",
+        }
+    }
+
+    /// The clap definition of the builtin's arguments, which usage and help text is generated from.
+    pub fn definition(&self) -> clap::Command {
+        let command = match self {
+            Self::Bg => bg::Cli::command(),
+            Self::Bracket => test::Cli::command(),
+            Self::Break => r#break::Cli::command(),
+            Self::Cd => cd::Cli::command(),
+            Self::Colon => colon::Cli::command(),
+            Self::Command => command::Cli::command(),
+            Self::Continue => r#continue::Cli::command(),
+            Self::Declare => declare::Cli::command(),
+            Self::Dirs => dirs::Cli::command(),
+            Self::Disown => disown::Cli::command(),
+            Self::Echo => echo::Cli::command(),
+            Self::Eval => eval::Cli::command(),
+            Self::Exec => exec::Cli::command(),
+            Self::Exit => exit::Cli::command(),
+            Self::False => r#false::Cli::command(),
+            Self::Fg => fg::Cli::command(),
+            Self::Getopts => getopts::Cli::command(),
+            Self::Hash => hash::Cli::command(),
+            Self::Help => help::Cli::command(),
+            Self::Jobs => jobs::Cli::command(),
+            Self::Kill => kill::Cli::command(),
+            Self::Local => declare::Cli::command().about("Define variables local to a function"),
+            Self::Popd => popd::Cli::command(),
+            Self::Printf => printf::Cli::command(),
+            Self::Pushd => pushd::Cli::command(),
+            Self::Pwd => pwd::Cli::command(),
+            Self::Read => read::Cli::command(),
+            Self::Return => r#return::Cli::command(),
+            Self::Set => set::Cli::command(),
+            Self::Shift => shift::Cli::command(),
+            Self::Shopt => shopt::Cli::command(),
+            Self::Source => source::Cli::command(),
+            Self::Test => test::Cli::command(),
+            Self::Times => times::Cli::command(),
+            Self::Trap => trap::Cli::command(),
+            Self::True => r#true::Cli::command(),
+            Self::Type => r#type::Cli::command(),
+            Self::Ulimit => ulimit::Cli::command(),
+            Self::Umask => umask::Cli::command(),
+            Self::Unset => unset::Cli::command(),
+            Self::Which => which::Cli::command(),
+        };
+
+        command.name(self.name()).bin_name(self.name())
+    }
+
+    pub fn run(&self, shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
         let f = match self {
+            Self::Bg => Self::bg,
+            Self::Bracket => Self::bracket,
+            Self::Break => Self::r#break,
             Self::Cd => Self::cd,
+            Self::Colon => Self::colon,
+            Self::Command => Self::command,
+            Self::Continue => Self::r#continue,
+            Self::Declare => Self::declare,
+            Self::Dirs => Self::dirs,
+            Self::Disown => Self::disown,
+            Self::Echo => Self::echo,
+            Self::Eval => Self::eval,
+            Self::Exec => Self::exec,
             Self::Exit => Self::exit,
+            Self::False => Self::r#false,
+            Self::Fg => Self::fg,
+            Self::Getopts => Self::getopts,
+            Self::Hash => Self::hash,
+            Self::Help => Self::help,
+            Self::Jobs => Self::jobs,
+            Self::Kill => Self::kill,
+            Self::Local => Self::local,
+            Self::Popd => Self::popd,
+            Self::Printf => Self::printf,
+            Self::Pushd => Self::pushd,
+            Self::Pwd => Self::pwd,
+            Self::Read => Self::read,
+            Self::Return => Self::r#return,
+            Self::Set => Self::set,
+            Self::Shift => Self::shift,
+            Self::Shopt => Self::shopt,
+            Self::Source => Self::source,
+            Self::Test => Self::test,
+            Self::Times => Self::times,
+            Self::Trap => Self::trap,
+            Self::True => Self::r#true,
+            Self::Type => Self::r#type,
+            Self::Ulimit => Self::ulimit,
+            Self::Umask => Self::umask,
+            Self::Unset => Self::unset,
             Self::Which => Self::which,
         };
 
+        if args.first() == Some(&"--help") && self.has_help_flag() {
+            io.print(self.help_text());
+            return ExitStatus::SUCCESS;
+        }
+
         f(shell, io, args)
     }
 }
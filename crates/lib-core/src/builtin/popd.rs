@@ -0,0 +1,61 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{
+    ExitStatus, Shell,
+    builtin::{Builtin, cd, dirs},
+};
+
+/// Remove directories from the directory stack
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Remove the Nth entry, counting from the top with +N or the bottom with -N
+    #[arg(allow_hyphen_values = true)]
+    index: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn popd(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["popd"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if shell.dirs.is_empty() {
+            io.eprintln("popd: directory stack empty");
+            return ExitStatus::FAILURE;
+        }
+
+        let i = match cli
+            .index
+            .as_deref()
+            .map(|spec| dirs::parse_index(shell, spec))
+        {
+            Some(Ok(i)) => i,
+            Some(Err(e)) => {
+                io.eprintln(format!("popd: {e}"));
+                return ExitStatus::FAILURE;
+            }
+            None => 0,
+        };
+
+        if i == 0 {
+            let top = shell.dirs[0].clone();
+            let code = cd::change_dir(shell, io, "popd", &top, false);
+
+            if !code.is_success() {
+                return code;
+            }
+
+            shell.dirs.remove(0);
+        } else {
+            shell.dirs.remove(i - 1);
+        }
+
+        dirs::print_stack(shell, io, false);
+        ExitStatus::SUCCESS
+    }
+}
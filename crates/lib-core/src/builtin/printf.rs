@@ -0,0 +1,504 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, parsing};
+
+use super::echo::unescape;
+
+/// Format and print arguments
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Assign the output to the variable VAR rather than printing it
+    #[arg(short = 'v', value_name = "VAR")]
+    var: Option<String>,
+
+    /// Format string containing literal text, escapes and conversion specifications
+    #[arg(allow_hyphen_values = true)]
+    format: String,
+
+    /// Arguments consumed by the conversions, the format is reused until they are exhausted
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn printf(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["printf"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if let Some(var) = &cli.var
+            && !parsing::Parser::check_name(var)
+        {
+            io.eprintln(format!("printf: `{var}': not a valid identifier"));
+            return ExitStatus::FAILURE;
+        }
+
+        let mut formatter = Formatter {
+            args: cli.args.iter(),
+            consumed: false,
+            errors: Vec::new(),
+        };
+
+        let mut output = Vec::new();
+
+        loop {
+            formatter.consumed = false;
+
+            if formatter.format(&cli.format, &mut output) {
+                break;
+            }
+
+            if !formatter.consumed || formatter.args.len() == 0 {
+                break;
+            }
+        }
+
+        let assigned = match &cli.var {
+            Some(var) => shell.set_var(var, &String::from_utf8_lossy(&output)),
+            None => {
+                _ = io.write(1, output);
+                true
+            }
+        };
+
+        for error in &formatter.errors {
+            io.eprintln(format!("printf: {error}"));
+        }
+
+        if formatter.errors.is_empty() && assigned {
+            ExitStatus::SUCCESS
+        } else {
+            ExitStatus::FAILURE
+        }
+    }
+}
+
+#[derive(Default)]
+struct Spec {
+    left: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+struct Formatter<'a> {
+    args: std::slice::Iter<'a, String>,
+    consumed: bool,
+    errors: Vec<String>,
+}
+
+impl<'a> Formatter<'a> {
+    fn next_arg(&mut self) -> Option<&'a str> {
+        let arg = self.args.next()?;
+        self.consumed = true;
+        Some(arg)
+    }
+
+    /// Formats one pass over `format`, returning true if output should stop (`\c`).
+    fn format(&mut self, format: &str, output: &mut Vec<u8>) -> bool {
+        let mut rest = format;
+
+        while !rest.is_empty() {
+            let bytes = rest.as_bytes();
+            let mut end = 0;
+
+            while end < bytes.len() && bytes[end] != b'%' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+
+            let end = end.min(bytes.len());
+            let (text, stop) = unescape(&rest[..end], true);
+            output.extend(text);
+
+            if stop {
+                return true;
+            }
+
+            rest = &rest[end..];
+
+            if let Some(spec) = rest.strip_prefix('%') {
+                match self.conversion(spec, output) {
+                    Some((remainder, stop)) => {
+                        if stop {
+                            return true;
+                        }
+
+                        rest = remainder;
+                    }
+                    None => return true,
+                }
+            }
+        }
+
+        false
+    }
+
+    fn conversion<'f>(&mut self, format: &'f str, output: &mut Vec<u8>) -> Option<(&'f str, bool)> {
+        let mut spec = Spec::default();
+        let mut chars = format.char_indices().peekable();
+
+        if let Some(remainder) = format.strip_prefix('%') {
+            output.push(b'%');
+            return Some((remainder, false));
+        }
+
+        while let Some((_, c)) = chars.next_if(|(_, c)| "-+ #0".contains(*c)) {
+            match c {
+                '-' => spec.left = true,
+                '+' => spec.plus = true,
+                ' ' => spec.space = true,
+                '#' => spec.alternate = true,
+                _ => spec.zero = true,
+            }
+        }
+
+        if chars.next_if(|(_, c)| *c == '*').is_some() {
+            let width = self.next_arg().map(|arg| self.integer(arg)).unwrap_or(0);
+            spec.left |= width < 0;
+            spec.width = width.unsigned_abs() as usize;
+        } else {
+            while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                spec.width = spec.width * 10 + c as usize - '0' as usize;
+            }
+        }
+
+        if chars.next_if(|(_, c)| *c == '.').is_some() {
+            if chars.next_if(|(_, c)| *c == '*').is_some() {
+                let precision = self.next_arg().map(|arg| self.integer(arg)).unwrap_or(0);
+                spec.precision = usize::try_from(precision).ok();
+            } else {
+                let mut precision = 0;
+
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    precision = precision * 10 + c as usize - '0' as usize;
+                }
+
+                spec.precision = Some(precision);
+            }
+        }
+
+        while chars.next_if(|(_, c)| "hlLjzt".contains(*c)).is_some() {}
+
+        let Some((i, conversion)) = chars.next() else {
+            self.errors
+                .push(String::from("`%': missing format character"));
+            return None;
+        };
+
+        let remainder = &format[i + conversion.len_utf8()..];
+
+        let text = match conversion {
+            'd' | 'i' => {
+                let value = self.next_arg().map(|arg| self.integer(arg)).unwrap_or(0);
+                let sign = sign(value < 0, &spec);
+                let digits = value.unsigned_abs().to_string();
+                integer(&spec, sign, &digits, value == 0)
+            }
+            'o' | 'u' | 'x' | 'X' => {
+                let value = self.next_arg().map(|arg| self.integer(arg)).unwrap_or(0) as u64;
+
+                let (prefix, digits) = match conversion {
+                    'o' if spec.alternate && value != 0 => ("0", format!("{value:o}")),
+                    'o' => ("", format!("{value:o}")),
+                    'x' if spec.alternate && value != 0 => ("0x", format!("{value:x}")),
+                    'x' => ("", format!("{value:x}")),
+                    'X' if spec.alternate && value != 0 => ("0X", format!("{value:X}")),
+                    'X' => ("", format!("{value:X}")),
+                    _ => ("", value.to_string()),
+                };
+
+                integer(&spec, prefix, &digits, value == 0)
+            }
+            'f' | 'F' | 'e' | 'E' | 'g' | 'G' => {
+                let value = self.next_arg().map(|arg| self.float(arg)).unwrap_or(0.0);
+                float(&spec, conversion, value)
+            }
+            'c' => {
+                let text = self
+                    .next_arg()
+                    .and_then(|arg| arg.chars().next())
+                    .map(String::from)
+                    .unwrap_or_default();
+
+                pad(&spec, "", &text, false)
+            }
+            's' => {
+                let arg = self.next_arg().unwrap_or_default();
+                pad(&spec, "", &truncate(arg, spec.precision), false)
+            }
+            'b' => {
+                let (mut text, stop) = unescape(self.next_arg().unwrap_or_default(), false);
+
+                if let Some(precision) = spec.precision {
+                    text.truncate(precision);
+                }
+
+                let fill = vec![b' '; spec.width.saturating_sub(text.len())];
+
+                match spec.left {
+                    true => output.extend(text.into_iter().chain(fill)),
+                    false => output.extend(fill.into_iter().chain(text)),
+                }
+
+                return Some((remainder, stop));
+            }
+            'q' => {
+                let arg = self.next_arg().unwrap_or_default();
+                pad(&spec, "", &quote(arg), false)
+            }
+            _ => {
+                self.errors
+                    .push(format!("`{conversion}': invalid format character"));
+                return None;
+            }
+        };
+
+        output.extend(text.as_bytes());
+        Some((remainder, false))
+    }
+
+    fn integer(&mut self, arg: &str) -> i64 {
+        let trimmed = arg.trim_start();
+
+        if trimmed.is_empty() {
+            return 0;
+        }
+
+        if let Some(quoted) = trimmed.strip_prefix(['\'', '"']) {
+            return quoted.chars().next().map(|c| c as i64).unwrap_or(0);
+        }
+
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let parsed = if let Some(hex) = unsigned
+            .strip_prefix("0x")
+            .or_else(|| unsigned.strip_prefix("0X"))
+        {
+            u64::from_str_radix(hex, 16)
+        } else if unsigned.len() > 1
+            && let Some(octal) = unsigned.strip_prefix('0')
+        {
+            u64::from_str_radix(octal, 8)
+        } else {
+            unsigned.parse::<u64>()
+        };
+
+        match parsed {
+            Ok(value) if negative => (value as i64).wrapping_neg(),
+            Ok(value) => value as i64,
+            Err(_) => {
+                self.errors.push(format!("{arg}: invalid number"));
+                0
+            }
+        }
+    }
+
+    fn float(&mut self, arg: &str) -> f64 {
+        let trimmed = arg.trim();
+
+        if let Some(quoted) = trimmed.strip_prefix(['\'', '"']) {
+            return quoted
+                .chars()
+                .next()
+                .map(|c| c as u32 as f64)
+                .unwrap_or(0.0);
+        }
+
+        match trimmed.parse::<f64>() {
+            Ok(value) => value,
+            Err(_)
+                if !trimmed.is_empty()
+                    && trimmed.trim_start_matches(['+', '-']).starts_with("0x") =>
+            {
+                self.integer(trimmed) as f64
+            }
+            Err(_) => {
+                self.errors.push(format!("{arg}: invalid number"));
+                0.0
+            }
+        }
+    }
+}
+
+fn sign(negative: bool, spec: &Spec) -> &'static str {
+    if negative {
+        "-"
+    } else if spec.plus {
+        "+"
+    } else if spec.space {
+        " "
+    } else {
+        ""
+    }
+}
+
+fn integer(spec: &Spec, prefix: &str, digits: &str, zero: bool) -> String {
+    let digits = match spec.precision {
+        Some(0) if zero => String::new(),
+        Some(precision) => format!("{digits:0>precision$}"),
+        None => digits.to_string(),
+    };
+
+    pad(spec, prefix, &digits, spec.precision.is_none())
+}
+
+fn float(spec: &Spec, conversion: char, value: f64) -> String {
+    let upper = conversion.is_ascii_uppercase();
+    let sign = sign(value.is_sign_negative() && !value.is_nan(), spec);
+    let value = value.abs();
+
+    if !value.is_finite() {
+        let text = if value.is_nan() { "nan" } else { "inf" };
+        let text = if upper {
+            text.to_uppercase()
+        } else {
+            text.to_string()
+        };
+        return pad(spec, sign, &text, false);
+    }
+
+    let precision = spec.precision.unwrap_or(6);
+
+    let mut text = match conversion.to_ascii_lowercase() {
+        'f' => format!("{value:.precision$}"),
+        'e' => exponential(value, precision),
+        _ => {
+            let precision = precision.max(1);
+            let exponent = exponent_of(value, precision - 1);
+
+            let text = if exponent < -4 || exponent >= precision as i32 {
+                exponential(value, precision - 1)
+            } else {
+                let decimals = (precision as i32 - 1 - exponent) as usize;
+                format!("{value:.decimals$}")
+            };
+
+            if spec.alternate {
+                text
+            } else {
+                strip_zeros(&text)
+            }
+        }
+    };
+
+    if spec.alternate && !text.contains('.') {
+        let at = text.find('e').unwrap_or(text.len());
+        text.insert(at, '.');
+    }
+
+    if upper {
+        text = text.to_uppercase();
+    }
+
+    pad(spec, sign, &text, true)
+}
+
+fn exponent_of(value: f64, precision: usize) -> i32 {
+    if value == 0.0 {
+        return 0;
+    }
+
+    let text = format!("{value:.precision$e}");
+    text.split_once('e')
+        .and_then(|(_, exponent)| exponent.parse().ok())
+        .unwrap_or(0)
+}
+
+fn exponential(value: f64, precision: usize) -> String {
+    let text = format!("{value:.precision$e}");
+
+    match text.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let exponent = exponent.parse::<i32>().unwrap_or(0);
+            let sign = if exponent < 0 { '-' } else { '+' };
+            format!("{mantissa}e{sign}{:02}", exponent.abs())
+        }
+        None => text,
+    }
+}
+
+fn strip_zeros(text: &str) -> String {
+    let (mantissa, exponent) = match text.find('e') {
+        Some(i) => text.split_at(i),
+        None => (text, ""),
+    };
+
+    let mantissa = if mantissa.contains('.') {
+        mantissa.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        mantissa
+    };
+
+    format!("{mantissa}{exponent}")
+}
+
+fn truncate(text: &str, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => text.chars().take(precision).collect(),
+        None => text.to_string(),
+    }
+}
+
+fn pad(spec: &Spec, prefix: &str, body: &str, zero_fill: bool) -> String {
+    let len = prefix.chars().count() + body.chars().count();
+    let fill = spec.width.saturating_sub(len);
+
+    if spec.left {
+        format!("{prefix}{body}{}", " ".repeat(fill))
+    } else if spec.zero && zero_fill {
+        format!("{prefix}{}{body}", "0".repeat(fill))
+    } else {
+        format!("{}{prefix}{body}", " ".repeat(fill))
+    }
+}
+
+fn quote(text: &str) -> String {
+    if text.is_empty() {
+        return String::from("''");
+    }
+
+    if text.chars().any(|c| c.is_control()) {
+        let mut quoted = String::from("$'");
+
+        for c in text.chars() {
+            match c {
+                '\n' => quoted.push_str("\\n"),
+                '\t' => quoted.push_str("\\t"),
+                '\r' => quoted.push_str("\\r"),
+                '\x1b' => quoted.push_str("\\E"),
+                '\'' | '\\' => {
+                    quoted.push('\\');
+                    quoted.push(c);
+                }
+                c if c.is_control() => quoted.push_str(&format!("\\{:03o}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+
+        quoted.push('\'');
+        return quoted;
+    }
+
+    let mut quoted = String::new();
+
+    for c in text.chars() {
+        if !c.is_alphanumeric() && !"%+,-./:=@_^".contains(c) {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted
+}
@@ -0,0 +1,84 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{
+    ExitStatus, Shell,
+    builtin::{Builtin, cd, dirs},
+};
+
+/// Add directories to the directory stack
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Directory to push, or +N/-N to rotate the stack so that the Nth entry is on top
+    #[arg(allow_hyphen_values = true)]
+    target: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn pushd(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["pushd"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let code = match cli.target.as_deref() {
+            None => {
+                let Some(top) = shell.dirs.first().cloned() else {
+                    io.eprintln("pushd: no other directory");
+                    return ExitStatus::FAILURE;
+                };
+
+                let code = cd::change_dir(shell, io, "pushd", &top, false);
+
+                if code.is_success() {
+                    shell.dirs[0] = shell.old_pwd.clone();
+                }
+
+                code
+            }
+            Some(spec) if spec.len() > 1 && spec.starts_with(['+', '-']) => {
+                let i = match dirs::parse_index(shell, spec) {
+                    Ok(i) => i,
+                    Err(e) => {
+                        io.eprintln(format!("pushd: {e}"));
+                        return ExitStatus::FAILURE;
+                    }
+                };
+
+                let mut stack = shell.directory_stack();
+                stack.rotate_left(i);
+                let code = cd::change_dir(shell, io, "pushd", &stack[0], false);
+
+                if code.is_success() {
+                    shell.dirs = stack.split_off(1);
+                }
+
+                code
+            }
+            Some(dir) => {
+                let path = if dir == "-" {
+                    shell.old_pwd.clone()
+                } else {
+                    dir.to_string()
+                };
+
+                let code = cd::change_dir(shell, io, "pushd", &path, false);
+
+                if code.is_success() {
+                    shell.dirs.insert(0, shell.old_pwd.clone());
+                }
+
+                code
+            }
+        };
+
+        if code.is_success() {
+            dirs::print_stack(shell, io, false);
+        }
+
+        code
+    }
+}
@@ -0,0 +1,36 @@
+use clap::Parser;
+use lib_os::{dir, io};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Print the current directory
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Print the logical path, which may contain symbolic links
+    #[arg(short = 'L', overrides_with = "physical")]
+    logical: bool,
+
+    /// Print the physical path with all symbolic links resolved
+    #[arg(short = 'P', overrides_with = "logical")]
+    physical: bool,
+}
+
+impl Builtin {
+    pub(super) fn pwd(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["pwd"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.physical {
+            io.println(dir::current());
+        } else {
+            io.println(&shell.pwd);
+        }
+
+        ExitStatus::SUCCESS
+    }
+}
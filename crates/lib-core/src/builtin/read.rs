@@ -0,0 +1,264 @@
+use std::{io::ErrorKind, time::Duration};
+
+use clap::Parser;
+use lib_os::io::{self, ByteReader};
+
+use crate::{ExitStatus, Shell, builtin::Builtin, parsing};
+
+/// Read a line from standard input into variables
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Variables to assign the fields to, the last receives the remainder of the line
+    names: Vec<String>,
+
+    /// Do not treat backslashes as escape characters
+    #[arg(short = 'r')]
+    raw: bool,
+
+    /// Print PROMPT to standard error before reading from a terminal
+    #[arg(short = 'p', value_name = "PROMPT")]
+    prompt: Option<String>,
+
+    /// Fail if a complete record is not read within TIMEOUT seconds
+    #[arg(short = 't', value_name = "TIMEOUT")]
+    timeout: Option<f64>,
+
+    /// Return after reading COUNT characters instead of a full record
+    #[arg(short = 'n', value_name = "COUNT")]
+    count: Option<usize>,
+
+    /// Read until the first character of DELIM rather than a newline
+    #[arg(short = 'd', value_name = "DELIM", allow_hyphen_values = true)]
+    delimiter: Option<String>,
+
+    /// Do not echo input coming from a terminal
+    #[arg(short = 's')]
+    silent: bool,
+
+    /// Assign the fields to sequential indices of ARRAY
+    #[arg(short = 'a', value_name = "ARRAY", conflicts_with = "names")]
+    array: Option<String>,
+
+    /// Read from file descriptor FD instead of standard input
+    #[arg(short = 'u', value_name = "FD")]
+    fd: Option<i32>,
+}
+
+impl Builtin {
+    pub(super) fn read(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["read"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        for name in cli.names.iter().chain(&cli.array) {
+            if !parsing::Parser::check_name(name) {
+                io.eprintln(format!("read: `{name}': not a valid identifier"));
+                return ExitStatus::FAILURE;
+            }
+        }
+
+        let timeout = match cli.timeout {
+            Some(secs) if secs >= 0.0 => Some(Duration::from_secs_f64(secs)),
+            Some(secs) => {
+                io.eprintln(format!("read: {secs}: invalid timeout specification"));
+                return ExitStatus::FAILURE;
+            }
+            None => None,
+        };
+
+        let delimiter = match cli.delimiter {
+            Some(delimiter) => delimiter.bytes().next().unwrap_or(0),
+            None => b'\n',
+        };
+
+        let fd = cli.fd.unwrap_or(0);
+        let handle = match io.get(fd).try_clone() {
+            Ok(handle) if !handle.is_closed() => handle,
+            _ => {
+                io.eprintln(format!("read: {fd}: invalid file descriptor"));
+                return ExitStatus::FAILURE;
+            }
+        };
+
+        let mut reader = ByteReader::new(&handle, timeout);
+
+        if let Some(prompt) = &cli.prompt
+            && reader.is_terminal()
+        {
+            io.eprint(prompt);
+            io.flush();
+        }
+
+        if timeout.is_some_and(|timeout| timeout.is_zero()) {
+            return match reader.ready() {
+                Ok(true) => ExitStatus::SUCCESS,
+                _ => ExitStatus::FAILURE,
+            };
+        }
+
+        if (cli.silent || cli.count.is_some())
+            && let Err(e) = reader.configure_terminal(!cli.silent, cli.count.is_none())
+        {
+            io.eprintln(format!("read: {e}"));
+            return ExitStatus::FAILURE;
+        }
+
+        let (record, code) = match read_record(&mut reader, delimiter, cli.count, cli.raw) {
+            Ok(result) => result,
+            Err(e) if e.kind() == ErrorKind::TimedOut => return ExitStatus::from_signal(14),
+            Err(e) => {
+                io.eprintln(format!("read: {e}"));
+                return ExitStatus::FAILURE;
+            }
+        };
+
+        drop(reader);
+        let ifs = shell.var("IFS").unwrap_or_else(|| String::from(" \t\n"));
+
+        if let Some(array) = &cli.array {
+            shell.set_array(array, &split_fields(&record, &ifs, None));
+        } else if cli.names.is_empty() {
+            let line = record.iter().map(|(c, _)| c).collect::<String>();
+            shell.set_var("REPLY", &line);
+        } else {
+            let fields = split_fields(&record, &ifs, Some(cli.names.len()));
+
+            for (i, name) in cli.names.iter().enumerate() {
+                let value = fields.get(i).map(|s| s.as_str()).unwrap_or_default();
+                shell.set_var(name, value);
+            }
+        }
+
+        code
+    }
+}
+
+type Record = Vec<(char, bool)>;
+
+fn read_record(
+    reader: &mut ByteReader,
+    delimiter: u8,
+    count: Option<usize>,
+    raw: bool,
+) -> std::io::Result<(Record, ExitStatus)> {
+    let mut bytes = Vec::new();
+    let mut escaped = Vec::new();
+    let mut chars = 0;
+    let mut pending = 0;
+
+    let found = loop {
+        // the count is in characters, so a multibyte character is completed before stopping
+        if pending == 0 && count.is_some_and(|count| chars >= count) {
+            break true;
+        }
+
+        let Some(byte) = reader.read_byte()? else {
+            break false;
+        };
+
+        let (byte, is_escaped) = if !raw && byte == b'\\' {
+            match reader.read_byte()? {
+                Some(b'\n') => continue,
+                Some(byte) => (byte, true),
+                None => break false,
+            }
+        } else if byte == delimiter {
+            break true;
+        } else {
+            (byte, false)
+        };
+
+        match byte {
+            0x80..=0xBF if pending > 0 => pending -= 1,
+            _ => {
+                chars += 1;
+
+                pending = match byte {
+                    0xC0..=0xDF => 1,
+                    0xE0..=0xEF => 2,
+                    0xF0..=0xF7 => 3,
+                    _ => 0,
+                };
+            }
+        }
+
+        bytes.push(byte);
+        escaped.push(is_escaped);
+    };
+
+    let record = match std::str::from_utf8(&bytes) {
+        Ok(text) => {
+            let mut flags = escaped.iter();
+            let mut record = Vec::new();
+
+            for c in text.chars() {
+                let escaped = flags.next().copied().unwrap_or(false);
+                flags.by_ref().take(c.len_utf8() - 1).for_each(drop);
+                record.push((c, escaped));
+            }
+
+            record
+        }
+        Err(_) => String::from_utf8_lossy(&bytes)
+            .chars()
+            .map(|c| (c, false))
+            .collect(),
+    };
+
+    let code = if found {
+        ExitStatus::SUCCESS
+    } else {
+        ExitStatus::FAILURE
+    };
+
+    Ok((record, code))
+}
+
+fn split_fields(record: &[(char, bool)], ifs: &str, limit: Option<usize>) -> Vec<String> {
+    let is_separator = |&(c, escaped): &(char, bool)| !escaped && ifs.contains(c);
+    let is_blank = |&(c, escaped): &(char, bool)| !escaped && ifs.contains(c) && c.is_whitespace();
+
+    let mut fields = Vec::new();
+    let mut rest = record;
+
+    while rest.first().is_some_and(is_blank) {
+        rest = &rest[1..];
+    }
+
+    while !rest.is_empty() {
+        if limit.is_some_and(|limit| fields.len() + 1 == limit) {
+            let mut end = rest.len();
+
+            while end > 0 && is_blank(&rest[end - 1]) {
+                end -= 1;
+            }
+
+            fields.push(rest[..end].iter().map(|(c, _)| c).collect());
+            break;
+        }
+
+        let end = rest.iter().position(is_separator).unwrap_or(rest.len());
+        fields.push(rest[..end].iter().map(|(c, _)| c).collect());
+        rest = &rest[end..];
+
+        while rest.first().is_some_and(is_blank) {
+            rest = &rest[1..];
+        }
+
+        if let Some(first) = rest.first()
+            && is_separator(first)
+        {
+            rest = &rest[1..];
+
+            while rest.first().is_some_and(is_blank) {
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    fields
+}
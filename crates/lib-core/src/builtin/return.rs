@@ -0,0 +1,43 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::Flow};
+
+/// Return from a function or sourced script
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Return status, defaulting to that of the last command
+    #[arg(allow_negative_numbers = true)]
+    status: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn r#return(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        if shell.source_depth == 0 && shell.function_depth == 0 {
+            io.eprintln("return: can only `return` from a function or sourced script");
+            return ExitStatus::FAILURE;
+        }
+
+        let cli = match Cli::try_parse_from(["return"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let code = match cli.status {
+            Some(arg) => match arg.parse::<i64>() {
+                Ok(code) => ExitStatus::new(code.rem_euclid(256) as u8),
+                Err(_) => {
+                    io.eprintln(format!("return: {arg}: numeric argument required"));
+                    ExitStatus::MISUSE
+                }
+            },
+            None => shell.exit_code,
+        };
+
+        shell.flow = Some(Flow::Return(code));
+        code
+    }
+}
@@ -0,0 +1,118 @@
+use clap::{Parser, ValueEnum};
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, ShellOption, builtin::Builtin};
+
+// Only describes the options, as set parses them itself to accept `+` to unset them
+/// Set shell options and positional parameters
+#[derive(Parser)]
+#[command(disable_help_flag = true)]
+pub(super) struct Cli {
+    /// New positional parameters
+    #[arg(allow_hyphen_values = true)]
+    args: Vec<String>,
+
+    /// Exit immediately when a command fails
+    #[arg(short = 'e')]
+    errexit: bool,
+
+    /// Treat expansion of unset parameters as an error
+    #[arg(short = 'u')]
+    nounset: bool,
+
+    /// Print commands and their arguments as they are executed
+    #[arg(short = 'x')]
+    xtrace: bool,
+
+    /// Prevent output redirection from overwriting existing files
+    #[arg(short = 'C')]
+    noclobber: bool,
+
+    /// Read commands without executing them
+    #[arg(short = 'n')]
+    noexec: bool,
+
+    /// Set the named option, or list options if no name is given
+    #[arg(short = 'o', value_name = "OPTION")]
+    option: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn set(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        if args.is_empty() {
+            for (name, variable) in shell.variables.iter() {
+                if let Some(value) = &variable.value {
+                    io.println(value.assignment(name));
+                }
+            }
+
+            return ExitStatus::SUCCESS;
+        }
+
+        let mut args = args.iter();
+        let mut positional = None;
+
+        while let Some(arg) = args.next() {
+            let value = match arg.chars().next() {
+                _ if *arg == "--" => {
+                    positional = Some(args.by_ref().collect::<Vec<_>>());
+                    break;
+                }
+                Some('-') if arg.len() > 1 => true,
+                Some('+') if arg.len() > 1 => false,
+                _ => {
+                    positional = Some([arg].into_iter().chain(args.by_ref()).collect());
+                    break;
+                }
+            };
+
+            for flag in arg.chars().skip(1) {
+                if flag == 'o' {
+                    let Some(name) = args.next() else {
+                        print_options(shell, io, value);
+                        continue;
+                    };
+
+                    match ShellOption::from_name(name).filter(ShellOption::is_set_option) {
+                        Some(option) => shell.set_option(option, value),
+                        None => {
+                            io.eprintln(format!("set: {name}: invalid option name"));
+                            return ExitStatus::MISUSE;
+                        }
+                    }
+                } else if let Some(option) = ShellOption::from_flag(flag) {
+                    shell.set_option(option, value);
+                } else {
+                    io.eprintln(format!("set: {}{flag}: invalid option", &arg[..1]));
+                    return ExitStatus::MISUSE;
+                }
+            }
+        }
+
+        if let Some(positional) = positional {
+            shell.args.truncate(1);
+            shell
+                .args
+                .extend(positional.into_iter().map(|arg| arg.to_string()));
+        }
+
+        ExitStatus::SUCCESS
+    }
+}
+
+fn print_options(shell: &Shell, io: &mut io::Context, value: bool) {
+    for option in ShellOption::value_variants()
+        .iter()
+        .filter(|option| option.is_set_option())
+    {
+        let enabled = shell.option(*option);
+
+        if value {
+            let state = if enabled { "on" } else { "off" };
+            io.println(format!("{:<15}\t{state}", option.name()));
+        } else {
+            let sign = if enabled { '-' } else { '+' };
+            io.println(format!("set {sign}o {}", option.name()));
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Shift positional parameters
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Number of positional parameters to drop
+    count: Option<String>,
+}
+
+impl Builtin {
+    pub(super) fn shift(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["shift"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let count = match cli.count {
+            Some(arg) => match arg.parse::<usize>() {
+                Ok(count) => count,
+                Err(_) => {
+                    io.eprintln(format!("shift: {arg}: numeric argument required"));
+                    return ExitStatus::FAILURE;
+                }
+            },
+            None => 1,
+        };
+
+        if count >= shell.args.len() {
+            return ExitStatus::FAILURE;
+        }
+
+        shell.args.drain(1..=count);
+        ExitStatus::SUCCESS
+    }
+}
@@ -0,0 +1,112 @@
+use clap::{Parser, ValueEnum};
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, ShellOption, builtin::Builtin};
+
+/// Set and unset shell options
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Option names to set, unset or report on
+    optname: Vec<String>,
+
+    /// Enable each named option
+    #[arg(short = 's', conflicts_with = "unset")]
+    set: bool,
+
+    /// Disable each named option
+    #[arg(short = 'u')]
+    unset: bool,
+
+    /// Print options in a form that can be reused as input
+    #[arg(short = 'p')]
+    print: bool,
+
+    /// Suppress output, the status indicates whether the options are set
+    #[arg(short = 'q')]
+    quiet: bool,
+
+    /// Restrict option names to those defined for `set -o`
+    #[arg(short = 'o')]
+    set_options: bool,
+}
+
+impl Builtin {
+    pub(super) fn shopt(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["shopt"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        // `-o` works on the options of `set -o` and otherwise on the remaining shopt options
+        let available = ShellOption::value_variants()
+            .iter()
+            .filter(|option| option.is_set_option() == cli.set_options)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut options = Vec::new();
+
+        for name in &cli.optname {
+            match ShellOption::from_name(name).filter(|option| available.contains(option)) {
+                Some(option) => options.push(option),
+                None => {
+                    io.eprintln(format!("shopt: {name}: invalid shell option name"));
+                    return ExitStatus::FAILURE;
+                }
+            }
+        }
+
+        if cli.set || cli.unset {
+            if options.is_empty() {
+                options = available
+                    .into_iter()
+                    .filter(|option| shell.option(*option) == cli.set)
+                    .collect();
+            } else {
+                for option in options {
+                    shell.set_option(option, cli.set);
+                }
+
+                return ExitStatus::SUCCESS;
+            }
+        } else if options.is_empty() {
+            options = available;
+        }
+
+        // only a query of named options reports through the status whether they are all set
+        let query = !cli.optname.is_empty();
+        let mut code = ExitStatus::SUCCESS;
+
+        for option in options {
+            let enabled = shell.option(option);
+
+            if query && !enabled {
+                code = ExitStatus::FAILURE;
+            }
+
+            if cli.quiet {
+                continue;
+            }
+
+            if cli.print {
+                let command = if cli.set_options { "set" } else { "shopt" };
+                let flag = match (cli.set_options, enabled) {
+                    (true, true) => "-o",
+                    (true, false) => "+o",
+                    (false, true) => "-s",
+                    (false, false) => "-u",
+                };
+
+                io.println(format!("{command} {flag} {}", option.name()));
+            } else {
+                let state = if enabled { "on" } else { "off" };
+                io.println(format!("{:<15}\t{state}", option.name()));
+            }
+        }
+
+        code
+    }
+}
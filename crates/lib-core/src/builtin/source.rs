@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use lib_os::io;
+
+use crate::{
+    ExitStatus, Shell,
+    builtin::Builtin,
+    shell::{Flow, Trap},
+};
+
+/// Run commands from a file in the current shell
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Script to execute in the current shell
+    filename: String,
+
+    /// Positional parameters to set while the script runs
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn source(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["source"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let Some(path) = find_script(shell, &cli.filename) else {
+            io.eprintln(format!("source: {}: file not found", cli.filename));
+            return ExitStatus::FAILURE;
+        };
+
+        let script = match std::fs::read_to_string(&path) {
+            Ok(script) => script,
+            Err(e) => {
+                io.eprintln(format!("source: {}: {e}", cli.filename));
+                return ExitStatus::FAILURE;
+            }
+        };
+
+        let saved_args = if cli.args.is_empty() {
+            None
+        } else {
+            let args = shell.args.split_off(1.min(shell.args.len()));
+            shell.args.extend(cli.args);
+            Some(args)
+        };
+
+        shell.source_depth += 1;
+        let mut code = shell.run_nested(&script);
+        shell.source_depth -= 1;
+
+        if let Some(Flow::Return(returned)) = shell.flow {
+            shell.flow = None;
+            code = returned;
+        }
+
+        if let Some(args) = saved_args {
+            shell.args.truncate(1);
+            shell.args.extend(args);
+        }
+
+        shell.exit_code = code;
+        shell.run_trap(Trap::Return);
+        code
+    }
+}
+
+fn find_script(shell: &Shell, filename: &str) -> Option<PathBuf> {
+    if filename.contains('/') {
+        return Some(PathBuf::from(filename));
+    }
+
+    shell
+        .var("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(filename))
+                .find(|path| path.is_file())
+        })
+        .or_else(|| Some(PathBuf::from(filename)).filter(|path| path.is_file()))
+}
@@ -0,0 +1,217 @@
+use std::{
+    fs,
+    os::unix::fs::{FileTypeExt, MetadataExt},
+};
+
+use clap::Parser;
+use lib_os::{
+    dir::{self, Access},
+    io,
+};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+// Only describes the arguments, as the expression's operators look like options
+/// Evaluate a conditional expression
+#[derive(Parser)]
+#[command(disable_help_flag = true)]
+pub(super) struct Cli {
+    /// Expression to evaluate, such as `-f FILE` or `STRING = STRING`
+    #[arg(allow_hyphen_values = true)]
+    expression: Vec<String>,
+}
+
+impl Builtin {
+    pub(super) fn test(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        evaluate("test", io, args)
+    }
+
+    pub(super) fn bracket(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        match args.split_last() {
+            Some((&"]", args)) => evaluate("[", io, args),
+            _ => {
+                io.eprintln("[: missing `]'");
+                ExitStatus::MISUSE
+            }
+        }
+    }
+}
+
+fn evaluate(name: &str, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+    match (Test { io }).evaluate(args) {
+        Ok(true) => ExitStatus::SUCCESS,
+        Ok(false) => ExitStatus::FAILURE,
+        Err(e) => {
+            io.eprintln(format!("{name}: {e}"));
+            ExitStatus::MISUSE
+        }
+    }
+}
+
+fn is_unary(op: &str) -> bool {
+    op.strip_prefix('-')
+        .is_some_and(|flag| flag.len() == 1 && "bcdefghkLnprsStuwxz".contains(flag))
+}
+
+fn is_binary(op: &str) -> bool {
+    [
+        "=", "==", "!=", "<", ">", "-eq", "-ne", "-lt", "-le", "-gt", "-ge", "-nt", "-ot", "-ef",
+    ]
+    .contains(&op)
+}
+
+struct Test<'a> {
+    io: &'a mut io::Context,
+}
+
+impl Test<'_> {
+    /// Applies the POSIX rules for up to four arguments, falling back to the XSI grammar beyond that.
+    fn evaluate(&mut self, args: &[&str]) -> Result<bool, String> {
+        match *args {
+            [] => Ok(false),
+            [arg] => Ok(!arg.is_empty()),
+            ["!", arg] => Ok(arg.is_empty()),
+            [op, arg] if is_unary(op) => self.unary(op, arg),
+            [op, _] => Err(format!("{op}: unary operator expected")),
+            [left, op, right] if is_binary(op) => self.binary(left, op, right),
+            [left, "-a", right] => Ok(!left.is_empty() && !right.is_empty()),
+            [left, "-o", right] => Ok(!left.is_empty() || !right.is_empty()),
+            ["!", ref rest @ ..] if args.len() <= 4 => self.evaluate(rest).map(|value| !value),
+            ["(", ref inner @ .., ")"] if args.len() <= 4 => self.evaluate(inner),
+            [_, op, _] => Err(format!("{op}: binary operator expected")),
+            _ => {
+                let mut rest = args;
+                let value = self.or(&mut rest)?;
+
+                match rest.first() {
+                    Some(arg) => Err(format!("{arg}: too many arguments")),
+                    None => Ok(value),
+                }
+            }
+        }
+    }
+
+    fn or(&mut self, args: &mut &[&str]) -> Result<bool, String> {
+        let mut value = self.and(args)?;
+
+        while let Some((&"-o", rest)) = args.split_first() {
+            *args = rest;
+            value |= self.and(args)?;
+        }
+
+        Ok(value)
+    }
+
+    fn and(&mut self, args: &mut &[&str]) -> Result<bool, String> {
+        let mut value = self.not(args)?;
+
+        while let Some((&"-a", rest)) = args.split_first() {
+            *args = rest;
+            value &= self.not(args)?;
+        }
+
+        Ok(value)
+    }
+
+    fn not(&mut self, args: &mut &[&str]) -> Result<bool, String> {
+        match args.split_first() {
+            Some((&"!", rest)) => {
+                *args = rest;
+                self.not(args).map(|value| !value)
+            }
+            _ => self.primary(args),
+        }
+    }
+
+    fn primary(&mut self, args: &mut &[&str]) -> Result<bool, String> {
+        match **args {
+            [] => Err(String::from("argument expected")),
+            ["(", ref rest @ ..] => {
+                *args = rest;
+                let value = self.or(args)?;
+
+                match args.split_first() {
+                    Some((&")", rest)) => {
+                        *args = rest;
+                        Ok(value)
+                    }
+                    _ => Err(String::from("`)' expected")),
+                }
+            }
+            [left, op, right, ref rest @ ..] if is_binary(op) => {
+                *args = rest;
+                self.binary(left, op, right)
+            }
+            [op, arg, ref rest @ ..] if is_unary(op) => {
+                *args = rest;
+                self.unary(op, arg)
+            }
+            [arg, ref rest @ ..] => {
+                *args = rest;
+                Ok(!arg.is_empty())
+            }
+        }
+    }
+
+    fn unary(&mut self, op: &str, arg: &str) -> Result<bool, String> {
+        let metadata = || fs::metadata(arg).ok();
+
+        Ok(match op {
+            "-z" => arg.is_empty(),
+            "-n" => !arg.is_empty(),
+            "-t" => match i32::try_from(integer(arg)?) {
+                Ok(fd) => self.io.get(fd).is_terminal(),
+                Err(_) => false,
+            },
+            "-e" => metadata().is_some(),
+            "-f" => metadata().is_some_and(|m| m.is_file()),
+            "-d" => metadata().is_some_and(|m| m.is_dir()),
+            "-s" => metadata().is_some_and(|m| m.len() > 0),
+            "-b" => metadata().is_some_and(|m| m.file_type().is_block_device()),
+            "-c" => metadata().is_some_and(|m| m.file_type().is_char_device()),
+            "-p" => metadata().is_some_and(|m| m.file_type().is_fifo()),
+            "-S" => metadata().is_some_and(|m| m.file_type().is_socket()),
+            "-u" => metadata().is_some_and(|m| m.mode() & 0o4000 != 0),
+            "-g" => metadata().is_some_and(|m| m.mode() & 0o2000 != 0),
+            "-k" => metadata().is_some_and(|m| m.mode() & 0o1000 != 0),
+            "-h" | "-L" => fs::symlink_metadata(arg).is_ok_and(|m| m.is_symlink()),
+            "-r" => dir::has_access(arg, Access::Read),
+            "-w" => dir::has_access(arg, Access::Write),
+            "-x" => dir::has_access(arg, Access::Execute),
+            _ => return Err(format!("{op}: unary operator expected")),
+        })
+    }
+
+    fn binary(&mut self, left: &str, op: &str, right: &str) -> Result<bool, String> {
+        let modified = |path: &str| fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        Ok(match op {
+            "=" | "==" => left == right,
+            "!=" => left != right,
+            "<" => left < right,
+            ">" => left > right,
+            "-eq" => integer(left)? == integer(right)?,
+            "-ne" => integer(left)? != integer(right)?,
+            "-lt" => integer(left)? < integer(right)?,
+            "-le" => integer(left)? <= integer(right)?,
+            "-gt" => integer(left)? > integer(right)?,
+            "-ge" => integer(left)? >= integer(right)?,
+            "-nt" => match (modified(left), modified(right)) {
+                (Some(left), Some(right)) => left > right,
+                (left, _) => left.is_some(),
+            },
+            "-ot" => match (modified(left), modified(right)) {
+                (Some(left), Some(right)) => left < right,
+                (_, right) => right.is_some(),
+            },
+            "-ef" => dir::is_same_file(left, right),
+            _ => return Err(format!("{op}: binary operator expected")),
+        })
+    }
+}
+
+fn integer(arg: &str) -> Result<i64, String> {
+    arg.trim()
+        .parse()
+        .map_err(|_| format!("{arg}: integer expression expected"))
+}
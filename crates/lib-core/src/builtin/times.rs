@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use clap::Parser;
+use lib_os::{
+    io,
+    resource::{self, Who},
+};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Display the user and system times used by the shell and its children
+#[derive(Parser)]
+pub(super) struct Cli {}
+
+fn format(time: Duration) -> String {
+    let millis = time.as_millis();
+    format!(
+        "{}m{}.{:03}s",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+impl Builtin {
+    pub(super) fn times(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        if let Err(e) = Cli::try_parse_from(["times"].iter().chain(args)) {
+            io.eprintln(e.to_string());
+            return ExitStatus::MISUSE;
+        }
+
+        for who in [Who::Shell, Who::Children] {
+            match resource::cpu_time(who) {
+                Ok(time) => io.println(format!("{} {}", format(time.user), format(time.system))),
+                Err(e) => {
+                    io.eprintln(format!("times: {e}"));
+                    return ExitStatus::FAILURE;
+                }
+            }
+        }
+
+        ExitStatus::SUCCESS
+    }
+}
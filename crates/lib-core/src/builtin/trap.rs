@@ -0,0 +1,97 @@
+use clap::Parser;
+use lib_os::{io, signal};
+
+use crate::{ExitStatus, Shell, builtin::Builtin, shell::Trap};
+
+/// Run commands when the shell receives signals
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Command to run, `-` to reset or an empty string to ignore
+    #[arg(allow_hyphen_values = true)]
+    action: Option<String>,
+
+    /// Signal names or numbers, or one of EXIT, ERR, DEBUG, RETURN
+    signals: Vec<String>,
+
+    /// Display the trap commands associated with each signal
+    #[arg(short = 'p')]
+    print: bool,
+
+    /// List signal names and their numbers
+    #[arg(short = 'l')]
+    list: bool,
+}
+
+impl Builtin {
+    pub(super) fn trap(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["trap"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.list {
+            for (number, name) in signal::list() {
+                io.println(format!("{number:>2}) SIG{name}"));
+            }
+
+            return ExitStatus::SUCCESS;
+        }
+
+        let mut code = ExitStatus::SUCCESS;
+
+        if cli.print || cli.action.is_none() {
+            let specs = cli.action.iter().chain(&cli.signals).collect::<Vec<_>>();
+
+            let traps = if specs.is_empty() {
+                shell.traps.iter().map(|(trap, _)| *trap).collect()
+            } else {
+                let mut traps = Vec::new();
+
+                for spec in specs {
+                    match Trap::parse(spec) {
+                        Some(trap) => traps.push(trap),
+                        None => {
+                            io.eprintln(format!("trap: {spec}: invalid signal specification"));
+                            code = ExitStatus::FAILURE;
+                        }
+                    }
+                }
+
+                traps
+            };
+
+            for trap in traps {
+                if let Some(action) = shell.traps.get(trap) {
+                    let action = action.replace('\'', "'\\''");
+                    io.println(format!("trap -- '{action}' {}", trap.name()));
+                }
+            }
+
+            return code;
+        }
+
+        let (action, specs) = match cli.action {
+            Some(action) if cli.signals.is_empty() => (None, vec![action]),
+            Some(action) if action == "-" => (None, cli.signals),
+            action => (action, cli.signals),
+        };
+
+        for spec in specs {
+            let Some(trap) = Trap::parse(&spec) else {
+                io.eprintln(format!("trap: {spec}: invalid signal specification"));
+                code = ExitStatus::FAILURE;
+                continue;
+            };
+
+            if let Err(e) = shell.traps.set(trap, action.clone()) {
+                io.eprintln(format!("trap: {spec}: {e}"));
+                code = ExitStatus::FAILURE;
+            }
+        }
+
+        code
+    }
+}
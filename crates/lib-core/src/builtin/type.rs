@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use lib_os::{dir, io};
+
+use crate::{ExitStatus, Shell, builtin::Builtin, parsing::Scanner};
+
+/// Describe how each name would be interpreted as a command
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Names of the commands to describe
+    names: Vec<String>,
+
+    /// Display every place that contains a command named NAME
+    #[arg(short = 'a')]
+    all: bool,
+
+    /// Display a single word: `keyword`, `function`, `builtin` or `file`
+    #[arg(short = 't', conflicts_with = "path")]
+    kind: bool,
+
+    /// Display the path of the file that would be executed, if any
+    #[arg(short = 'p')]
+    path: bool,
+}
+
+pub(super) enum Kind {
+    Keyword,
+    Function,
+    Builtin,
+    Hashed(PathBuf),
+    File(PathBuf),
+}
+
+impl Kind {
+    pub(super) fn describe(&self, name: &str) -> String {
+        match self {
+            Self::Keyword => format!("{name} is a shell keyword"),
+            Self::Function => format!("{name} is a function"),
+            Self::Builtin => format!("{name} is a shell builtin"),
+            Self::Hashed(path) => format!("{name} is hashed ({})", path.display()),
+            Self::File(path) => format!("{name} is {}", path.display()),
+        }
+    }
+
+    pub(super) fn path(&self) -> Option<&PathBuf> {
+        match self {
+            Self::Hashed(path) | Self::File(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// Finds what `name` refers to in the order the shell would try, without updating the hash table.
+pub(super) fn resolve(shell: &mut Shell, name: &str, all: bool) -> Vec<Kind> {
+    if name.contains('/') {
+        return if dir::is_executable(name) {
+            vec![Kind::File(PathBuf::from(name))]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let mut kinds = Vec::new();
+
+    if Scanner::is_reserved_word(name) {
+        kinds.push(Kind::Keyword);
+    }
+
+    if shell.functions.contains_key(name) {
+        kinds.push(Kind::Function);
+    }
+
+    if Builtin::get(name).is_some() {
+        kinds.push(Kind::Builtin);
+    }
+
+    shell.commands.validate(shell.var("PATH"));
+
+    if all {
+        kinds.extend(shell.commands.search_all(name).into_iter().map(Kind::File));
+    } else if let Some(hashed) = shell.commands.get(name) {
+        kinds.push(Kind::Hashed(hashed.path.clone()));
+    } else if let Some(path) = shell.commands.search(name) {
+        kinds.push(Kind::File(path));
+    }
+
+    kinds
+}
+
+impl Builtin {
+    pub(super) fn r#type(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["type"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let mut code = ExitStatus::SUCCESS;
+
+        for name in &cli.names {
+            let mut kinds = resolve(shell, name, cli.all);
+
+            if kinds.is_empty() {
+                if !cli.kind && !cli.path {
+                    io.eprintln(format!("type: {name}: not found"));
+                }
+
+                code = ExitStatus::FAILURE;
+                continue;
+            }
+
+            if !cli.all {
+                kinds.truncate(1);
+            }
+
+            for kind in kinds {
+                if cli.kind {
+                    io.println(match kind {
+                        Kind::Keyword => "keyword",
+                        Kind::Function => "function",
+                        Kind::Builtin => "builtin",
+                        _ => "file",
+                    });
+                } else if cli.path {
+                    if let Some(path) = kind.path() {
+                        io.println(path.display().to_string());
+                    }
+                } else {
+                    io.println(kind.describe(name));
+                }
+            }
+        }
+
+        code
+    }
+}
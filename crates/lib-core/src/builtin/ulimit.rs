@@ -0,0 +1,236 @@
+use clap::Parser;
+use lib_os::{
+    io,
+    resource::{self, Limit, Resource},
+};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Display or modify shell resource limits
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// New limit, or one of `unlimited`, `hard` or `soft`
+    limit: Option<String>,
+
+    /// Use the hard limit
+    #[arg(short = 'H')]
+    hard: bool,
+
+    /// Use the soft limit
+    #[arg(short = 'S')]
+    soft: bool,
+
+    /// Report all current limits
+    #[arg(short = 'a')]
+    all: bool,
+
+    /// The maximum size of core files created
+    #[arg(short = 'c')]
+    core: bool,
+
+    /// The maximum size of a process's data segment
+    #[arg(short = 'd')]
+    data: bool,
+
+    /// The maximum size of files written by the shell and its children
+    #[arg(short = 'f')]
+    file: bool,
+
+    /// The maximum number of open file descriptors
+    #[arg(short = 'n')]
+    open: bool,
+
+    /// The maximum stack size
+    #[arg(short = 's')]
+    stack: bool,
+
+    /// The maximum amount of cpu time in seconds
+    #[arg(short = 't')]
+    cpu: bool,
+
+    /// The maximum amount of virtual memory available to the shell
+    #[arg(short = 'v')]
+    virtual_memory: bool,
+}
+
+struct Entry {
+    option: char,
+    resource: Resource,
+    description: &'static str,
+    unit: Option<&'static str>,
+    scale: u64,
+}
+
+const ENTRIES: [Entry; 7] = [
+    Entry {
+        option: 'c',
+        resource: Resource::CoreSize,
+        description: "core file size",
+        unit: Some("blocks"),
+        scale: 1024,
+    },
+    Entry {
+        option: 'd',
+        resource: Resource::DataSize,
+        description: "data seg size",
+        unit: Some("kbytes"),
+        scale: 1024,
+    },
+    Entry {
+        option: 'f',
+        resource: Resource::FileSize,
+        description: "file size",
+        unit: Some("blocks"),
+        scale: 1024,
+    },
+    Entry {
+        option: 'n',
+        resource: Resource::OpenFiles,
+        description: "open files",
+        unit: None,
+        scale: 1,
+    },
+    Entry {
+        option: 's',
+        resource: Resource::StackSize,
+        description: "stack size",
+        unit: Some("kbytes"),
+        scale: 1024,
+    },
+    Entry {
+        option: 't',
+        resource: Resource::CpuTime,
+        description: "cpu time",
+        unit: Some("seconds"),
+        scale: 1,
+    },
+    Entry {
+        option: 'v',
+        resource: Resource::VirtualMemory,
+        description: "virtual memory",
+        unit: Some("kbytes"),
+        scale: 1024,
+    },
+];
+
+impl Cli {
+    fn selects(&self, option: char) -> bool {
+        match option {
+            'c' => self.core,
+            'd' => self.data,
+            'f' => self.file,
+            'n' => self.open,
+            's' => self.stack,
+            't' => self.cpu,
+            _ => self.virtual_memory,
+        }
+    }
+}
+
+impl Entry {
+    fn label(&self) -> String {
+        let unit = match self.unit {
+            Some(unit) => format!("({unit}, -{}) ", self.option),
+            None => format!("(-{}) ", self.option),
+        };
+
+        format!("{:<20} {unit:>20}", self.description)
+    }
+
+    fn display(&self, limit: Limit) -> String {
+        match limit {
+            Some(value) => (value / self.scale).to_string(),
+            None => "unlimited".to_string(),
+        }
+    }
+
+    /// Parses a new limit for this resource, resolving `hard` and `soft` against the current limits.
+    fn parse(&self, value: &str, current: (Limit, Limit)) -> Result<Limit, String> {
+        match value {
+            "unlimited" => Ok(None),
+            "hard" => Ok(current.1),
+            "soft" => Ok(current.0),
+            _ => match value.parse::<u64>() {
+                Ok(number) => number
+                    .checked_mul(self.scale)
+                    .map(Some)
+                    .ok_or_else(|| format!("{value}: limit out of range")),
+                Err(_) => Err(format!("{value}: invalid number")),
+            },
+        }
+    }
+}
+
+impl Builtin {
+    pub(super) fn ulimit(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["ulimit"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let mut selected: Vec<&Entry> = ENTRIES
+            .iter()
+            .filter(|entry| cli.all || cli.selects(entry.option))
+            .collect();
+
+        if selected.is_empty() {
+            selected.extend(ENTRIES.iter().filter(|entry| entry.option == 'f'));
+        }
+
+        let labelled = selected.len() > 1;
+        let mut code = ExitStatus::SUCCESS;
+
+        for entry in selected {
+            let current = match resource::get_limit(entry.resource) {
+                Ok(current) => current,
+                Err(e) => {
+                    io.eprintln(format!(
+                        "ulimit: {}: cannot get limit: {e}",
+                        entry.description
+                    ));
+                    code = ExitStatus::FAILURE;
+                    continue;
+                }
+            };
+
+            let Some(value) = cli.limit.as_deref().filter(|_| !cli.all) else {
+                let limit = match cli.hard && !cli.soft {
+                    true => current.1,
+                    false => current.0,
+                };
+
+                match labelled {
+                    true => io.println(format!("{}{}", entry.label(), entry.display(limit))),
+                    false => io.println(entry.display(limit)),
+                }
+
+                continue;
+            };
+
+            let limit = match entry.parse(value, current) {
+                Ok(limit) => limit,
+                Err(e) => {
+                    io.eprintln(format!("ulimit: {e}"));
+                    return ExitStatus::FAILURE;
+                }
+            };
+
+            let both = !cli.hard && !cli.soft;
+            let soft = if cli.soft || both { limit } else { current.0 };
+            let hard = if cli.hard || both { limit } else { current.1 };
+
+            if let Err(e) = resource::set_limit(entry.resource, soft, hard) {
+                io.eprintln(format!(
+                    "ulimit: {}: cannot modify limit: {e}",
+                    entry.description
+                ));
+                code = ExitStatus::FAILURE;
+            }
+        }
+
+        code
+    }
+}
@@ -0,0 +1,142 @@
+use clap::Parser;
+use lib_os::{io, resource};
+
+use crate::{ExitStatus, Shell, builtin::Builtin};
+
+/// Display or set the file mode creation mask
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Octal or symbolic mode to set the mask to
+    mode: Option<String>,
+
+    /// Print the mask symbolically, as the permissions it allows
+    #[arg(short = 'S')]
+    symbolic: bool,
+
+    /// Print the mask in a form that may be reused as input
+    #[arg(short = 'p')]
+    reusable: bool,
+}
+
+/// Formats a mask as the permissions it leaves, ie. `u=rwx,g=rx,o=rx`.
+fn symbolic(mask: u32) -> String {
+    let allowed = !mask & 0o777;
+
+    [('u', 6), ('g', 3), ('o', 0)]
+        .iter()
+        .map(|(who, shift)| {
+            let bits = allowed >> shift;
+            let perms: String = [('r', 4), ('w', 2), ('x', 1)]
+                .iter()
+                .filter(|(_, bit)| bits & bit != 0)
+                .map(|(perm, _)| *perm)
+                .collect();
+
+            format!("{who}={perms}")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Applies a symbolic mode such as `g+w,o=` to the permissions allowed by `mask`, returning the
+/// resulting mask.
+fn parse_symbolic(mode: &str, mask: u32) -> Result<u32, String> {
+    let mut allowed = !mask & 0o777;
+
+    for clause in mode.split(',') {
+        let mut chars = clause.chars().peekable();
+        let mut who = 0;
+
+        while let Some(c) = chars.next_if(|c| "ugoa".contains(*c)) {
+            who |= match c {
+                'u' => 0o700,
+                'g' => 0o070,
+                'o' => 0o007,
+                _ => 0o777,
+            };
+        }
+
+        if who == 0 {
+            who = 0o777;
+        }
+
+        let op = match chars.next() {
+            Some(op @ ('+' | '-' | '=')) => op,
+            Some(c) => return Err(format!("`{c}': invalid symbolic mode operator")),
+            None => return Err(format!("`{clause}': invalid symbolic mode operator")),
+        };
+
+        let mut bits = 0;
+
+        for c in chars {
+            bits |= match c {
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' => 0o111,
+                _ => return Err(format!("`{c}': invalid symbolic mode character")),
+            };
+        }
+
+        allowed = match op {
+            '+' => allowed | (bits & who),
+            '-' => allowed & !(bits & who),
+            _ => (allowed & !who) | (bits & who),
+        };
+    }
+
+    Ok(!allowed & 0o777)
+}
+
+fn parse(mode: &str, mask: u32) -> Result<u32, String> {
+    if !mode.starts_with(|c: char| c.is_ascii_digit()) {
+        return parse_symbolic(mode, mask);
+    }
+
+    match u32::from_str_radix(mode, 8) {
+        Ok(mask) => Ok(mask & 0o777),
+        Err(_) => Err(format!("{mode}: octal number out of range")),
+    }
+}
+
+impl Builtin {
+    pub(super) fn umask(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["umask"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        let mut mask = resource::get_umask();
+
+        if let Some(mode) = &cli.mode {
+            mask = match parse(mode, mask) {
+                Ok(mask) => mask,
+                Err(e) => {
+                    io.eprintln(format!("umask: {e}"));
+                    return ExitStatus::FAILURE;
+                }
+            };
+
+            resource::set_umask(mask);
+
+            if !cli.symbolic {
+                return ExitStatus::SUCCESS;
+            }
+        }
+
+        let formatted = match cli.symbolic {
+            true => symbolic(mask),
+            false => format!("{mask:04o}"),
+        };
+
+        match (cli.reusable, cli.symbolic) {
+            (true, true) => io.println(format!("umask -S {formatted}")),
+            (true, false) => io.println(format!("umask {formatted}")),
+            _ => io.println(formatted),
+        }
+
+        ExitStatus::SUCCESS
+    }
+}
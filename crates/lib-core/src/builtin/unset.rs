@@ -0,0 +1,70 @@
+use clap::Parser;
+use lib_os::io;
+
+use crate::{ExitStatus, Shell, builtin::Builtin, parsing};
+
+/// Unset variables
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Variables or array elements to unset
+    #[arg(value_name = "NAME")]
+    names: Vec<String>,
+
+    /// Treat each NAME as a variable
+    #[arg(short = 'v')]
+    variables: bool,
+
+    /// Treat each NAME as a function
+    #[arg(short = 'f', conflicts_with = "variables")]
+    functions: bool,
+}
+
+impl Builtin {
+    pub(super) fn unset(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["unset"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        if cli.functions {
+            for name in &cli.names {
+                shell.functions.remove(name);
+            }
+
+            return ExitStatus::SUCCESS;
+        }
+
+        let mut code = ExitStatus::SUCCESS;
+
+        for arg in &cli.names {
+            let (name, key) = match arg.split_once('[') {
+                Some((name, rest)) => match rest.strip_suffix(']') {
+                    Some(key) => (name, Some(key)),
+                    None => (arg.as_str(), None),
+                },
+                None => (arg.as_str(), None),
+            };
+
+            if !parsing::Parser::check_name(name) {
+                io.eprintln(format!("unset: `{arg}': not a valid identifier"));
+                code = ExitStatus::FAILURE;
+                continue;
+            }
+
+            let result = match key {
+                Some("@" | "*") | None => shell.variables.unset(name),
+                Some(key) => shell.unset_element(name, key),
+            };
+
+            if let Err(e) = result {
+                io.eprintln(format!("unset: {arg}: {e}"));
+                code = ExitStatus::FAILURE;
+            }
+        }
+
+        code
+    }
+}
@@ -1,21 +1,57 @@
-use sysexits::ExitCode;
+use clap::Parser;
+use lib_os::io;
 
-use lib_os::{dir, io};
+use crate::{ExitStatus, Shell, builtin::Builtin};
 
-use crate::{builtin::Builtin, Shell};
+/// Locate commands on PATH
+#[derive(Parser)]
+pub(super) struct Cli {
+    /// Names of the commands to locate
+    names: Vec<String>,
+
+    /// Print every match rather than only the first
+    #[arg(short = 'a')]
+    all: bool,
+}
 
 impl Builtin {
-    pub(super) fn which(_shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitCode {
-        if let Some(keyword) = args.first() {
-            if Builtin::get(keyword).is_some() {
+    pub(super) fn which(shell: &mut Shell, io: &mut io::Context, args: &[&str]) -> ExitStatus {
+        let cli = match Cli::try_parse_from(["which"].iter().chain(args)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                io.eprintln(e.to_string());
+                return ExitStatus::MISUSE;
+            }
+        };
+
+        for keyword in &cli.names {
+            let builtin = Builtin::get(keyword).is_some();
+
+            if builtin {
                 io.println(format!("{keyword}: shell builtin"));
-            } else if let Some(path) = dir::find_on_path(keyword) {
-                io.println(path.display().to_string());
+
+                if !cli.all {
+                    continue;
+                }
+            }
+
+            shell.commands.validate(shell.var("PATH"));
+
+            let paths = if cli.all {
+                shell.commands.search_all(keyword)
             } else {
+                shell.commands.search(keyword).into_iter().collect()
+            };
+
+            for path in &paths {
+                io.println(path.display().to_string());
+            }
+
+            if paths.is_empty() && !builtin {
                 io.println(format!("{keyword} not found"));
             }
         }
 
-        ExitCode::Ok
+        ExitStatus::SUCCESS
     }
 }
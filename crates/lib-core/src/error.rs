@@ -2,7 +2,7 @@
 
 use derive_more::From;
 
-use crate::parsing::ParseErrorVariant;
+use crate::{ExitStatus, parsing::ParseErrorVariant, shell::Flow};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -23,12 +23,11 @@ impl SourceErrorVariant {
             SourceErrorVariant::Parse(ParseErrorVariant::UnmatchedParenthesis) => {
                 "Unmatched parenthesis"
             }
-            SourceErrorVariant::Parse(ParseErrorVariant::UnmatchedBrace) => {
-                "Unmatched brace"
-            },
+            SourceErrorVariant::Parse(ParseErrorVariant::UnmatchedBrace) => "Unmatched brace",
+            SourceErrorVariant::Parse(ParseErrorVariant::UnmatchedQuote) => "Unmatched quote",
             SourceErrorVariant::Parse(ParseErrorVariant::InvalidName) => {
                 "Name must not start with a number and must consist only of alphanumeric characters or '_'"
-            },
+            }
         }
     }
 }
@@ -64,6 +63,12 @@ pub enum Error {
         string: String,
     },
 
+    Aborted {
+        code: ExitStatus,
+    },
+
+    Flow(Flow),
+
     #[from]
     Source(SourceError),
 
@@ -75,6 +80,8 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Error::Adhoc { .. } => None,
+            Error::Aborted { .. } => None,
+            Error::Flow(_) => None,
             Error::Io(error) => error.source(),
             Error::Source(error) => error.source(),
         }
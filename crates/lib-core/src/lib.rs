@@ -3,7 +3,10 @@ mod config;
 mod error;
 mod parsing;
 mod shell;
+mod status;
 
+pub use builtin::Builtin;
 pub use error::Result;
-pub use shell::Shell;
 pub use parsing::ParsingIterator;
+pub use shell::{Shell, ShellOption};
+pub use status::ExitStatus;
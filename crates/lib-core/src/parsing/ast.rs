@@ -3,8 +3,11 @@ pub enum Node<'source> {
     Command {
         command: Command<'source>,
     },
+    Assignment {
+        assignments: Vec<Assignment<'source>>,
+    },
     Redirection {
-        redirections: Vec<Redirection>,
+        redirections: Vec<Redirection<'source>>,
         node: Box<Node<'source>>,
     },
     List {
@@ -16,6 +19,9 @@ pub enum Node<'source> {
     Subshell {
         node: Box<Node<'source>>,
     },
+    Background {
+        node: Box<Node<'source>>,
+    },
     Or {
         left: Box<Node<'source>>,
         right: Box<Node<'source>>,
@@ -34,14 +40,24 @@ pub enum Node<'source> {
     },
     Arithmetic,  // todo
     Conditional, // todo
-    For,         // todo
-    Case,        // todo
-    Function,    // todo
-    Coproc,      // todo
-    If,          // todo
-    Group,       // todo
-    Select,      // todo
-    Timespec,    // todo
+    For {
+        name: &'source str,
+        words: Option<Vec<Word<'source>>>,
+        body: Box<Node<'source>>,
+    },
+    Case, // todo
+    Function {
+        name: &'source str,
+        /// Source text of the body, as the function outlives the tree it was defined in.
+        body: &'source str,
+    },
+    Coproc, // todo
+    If,     // todo
+    Group {
+        node: Box<Node<'source>>,
+    },
+    Select,   // todo
+    Timespec, // todo
 }
 
 #[derive(Debug, Clone)]
@@ -56,15 +72,221 @@ pub enum Word<'source> {
 pub enum Parameter<'source> {
     Number(usize),
     String(&'source str),
+    Element {
+        name: &'source str,
+        subscript: Subscript<'source>,
+    },
+    Length(Box<Parameter<'source>>),
+    Keys(&'source str),
+    Status,
+    Flags,
     MyHome,
     OtherHome(Box<Word<'source>>),
 }
 
+#[derive(Debug, Clone)]
+pub enum Subscript<'source> {
+    At,
+    Star,
+    Index(Box<Word<'source>>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Assignment<'source> {
+    pub name: &'source str,
+    pub subscript: Option<Word<'source>>,
+    pub append: bool,
+    pub value: AssignmentValue<'source>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AssignmentValue<'source> {
+    Scalar(Word<'source>),
+    Array(Vec<(Option<Word<'source>>, Word<'source>)>),
+}
+
 #[derive(Debug, Clone)]
 pub struct Command<'source> {
     pub name: Box<Word<'source>>,
     pub args: Vec<Word<'source>>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RedirectionOperator {
+    Input,
+    Output,
+    Append,
+    Clobber,
+    ReadWrite,
+    DupInput,
+    DupOutput,
+}
+
 #[derive(Debug, Clone)]
-pub struct Redirection;
+pub struct Redirection<'source> {
+    pub fd: Option<i32>,
+    pub operator: RedirectionOperator,
+    pub target: Word<'source>,
+}
+
+impl std::fmt::Display for Node<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn join(
+            f: &mut std::fmt::Formatter<'_>,
+            nodes: &[Node],
+            separator: &str,
+        ) -> std::fmt::Result {
+            for (i, node) in nodes.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "{separator}")?;
+                }
+
+                write!(f, "{node}")?;
+            }
+
+            Ok(())
+        }
+
+        match self {
+            Node::Command { command } => write!(f, "{command}"),
+            Node::Assignment { assignments } => {
+                for (i, assignment) in assignments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+
+                    write!(f, "{assignment}")?;
+                }
+
+                Ok(())
+            }
+            Node::Redirection { redirections, node } => {
+                write!(f, "{node}")?;
+                redirections.iter().try_for_each(|r| write!(f, " {r}"))
+            }
+            Node::List { nodes } => join(f, nodes, "; "),
+            Node::Pipeline { nodes } => join(f, nodes, " | "),
+            Node::Subshell { node } => write!(f, "({node})"),
+            Node::Background { node } => write!(f, "{node} &"),
+            Node::Or { left, right } => write!(f, "{left} || {right}"),
+            Node::And { left, right } => write!(f, "{left} && {right}"),
+            Node::While { predicate, body } => write!(f, "while {predicate}; do {body}; done"),
+            Node::Until { predicate, body } => write!(f, "until {predicate}; do {body}; done"),
+            Node::For { name, words, body } => {
+                write!(f, "for {name}")?;
+
+                if let Some(words) = words {
+                    write!(f, " in")?;
+                    words.iter().try_for_each(|w| write!(f, " {w}"))?;
+                }
+
+                write!(f, "; do {body}; done")
+            }
+            Node::Function { name, body } => write!(f, "{name}() {body}"),
+            Node::Group { node } => write!(f, "{{ {node}; }}"),
+            node => write!(f, "{node:?}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Word<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Word::String(s) => write!(f, "{s}"),
+            Word::Parameter(p) => write!(f, "{p}"),
+            Word::Command { node } => write!(f, "$({node})"),
+            Word::Compound { words } => words.iter().try_for_each(|w| write!(f, "{w}")),
+        }
+    }
+}
+
+impl std::fmt::Display for Parameter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Parameter::Number(n) => write!(f, "${n}"),
+            Parameter::String(s) => write!(f, "${s}"),
+            Parameter::Element { name, subscript } => write!(f, "${{{name}[{subscript}]}}"),
+            Parameter::Length(p) => match &**p {
+                Parameter::Element { name, subscript } => write!(f, "${{#{name}[{subscript}]}}"),
+                p => write!(f, "${{#{}}}", &p.to_string()[1..]),
+            },
+            Parameter::Keys(name) => write!(f, "${{!{name}[@]}}"),
+            Parameter::Status => write!(f, "$?"),
+            Parameter::Flags => write!(f, "$-"),
+            Parameter::MyHome => write!(f, "~"),
+            Parameter::OtherHome(user) => write!(f, "~{user}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Subscript<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subscript::At => write!(f, "@"),
+            Subscript::Star => write!(f, "*"),
+            Subscript::Index(word) => write!(f, "{word}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Assignment<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if let Some(subscript) = &self.subscript {
+            write!(f, "[{subscript}]")?;
+        }
+
+        if self.append {
+            write!(f, "+")?;
+        }
+
+        match &self.value {
+            AssignmentValue::Scalar(word) => write!(f, "={word}"),
+            AssignmentValue::Array(elements) => {
+                write!(f, "=(")?;
+
+                for (i, (subscript, word)) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+
+                    if let Some(subscript) = subscript {
+                        write!(f, "[{subscript}]=")?;
+                    }
+
+                    write!(f, "{word}")?;
+                }
+
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Command<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        self.args.iter().try_for_each(|arg| write!(f, " {arg}"))
+    }
+}
+
+impl std::fmt::Display for Redirection<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = match self.operator {
+            RedirectionOperator::Input => "<",
+            RedirectionOperator::Output => ">",
+            RedirectionOperator::Append => ">>",
+            RedirectionOperator::Clobber => ">|",
+            RedirectionOperator::ReadWrite => "<>",
+            RedirectionOperator::DupInput => "<&",
+            RedirectionOperator::DupOutput => ">&",
+        };
+
+        if let Some(fd) = self.fd {
+            write!(f, "{fd}")?;
+        }
+
+        write!(f, "{operator}{}", self.target)
+    }
+}
@@ -6,7 +6,10 @@ mod parser;
 mod scanner;
 mod token;
 
-pub(crate) use ast::{Command, Node, Parameter, Redirection, Word};
+pub(crate) use ast::{
+    Assignment, AssignmentValue, Command, Node, Parameter, Redirection, RedirectionOperator,
+    Subscript, Word,
+};
 pub use iterator::ParsingIterator;
 pub(crate) use parser::{ParseErrorVariant, Parser};
 pub(crate) use scanner::Scanner;
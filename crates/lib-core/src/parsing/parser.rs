@@ -1,6 +1,9 @@
 use crate::{
     error::{SourceError, SourceErrorVariant},
-    parsing::{Command, Node, Parameter, ParsingIterator, Scanner, Token, TokenVariant, Word},
+    parsing::{
+        Assignment, AssignmentValue, Command, Node, Parameter, ParsingIterator, Redirection,
+        RedirectionOperator, Scanner, Subscript, Token, TokenVariant, Word,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -9,6 +12,7 @@ pub enum ParseErrorVariant {
     UnexpectedTokens,
     UnmatchedParenthesis,
     UnmatchedBrace,
+    UnmatchedQuote,
     InvalidName,
 }
 
@@ -71,14 +75,6 @@ impl ParsingIterator for Parser<'_> {
         self.current_token_value = self.next_token_value.take();
         self.next_token_value = Some(self.scanner.next_token());
 
-        if let Some(t) = self.current_token_value.clone() {
-            println!(
-                "{:?} -> {:?}",
-                self.lexeme(t.span).unwrap_or_default(),
-                t.variant
-            );
-        }
-
         self.current_token_value.as_ref()
     }
 
@@ -124,43 +120,211 @@ impl<'source> Parser<'source> {
         self.lexeme(span).map(Word::String)
     }
 
-    fn check_name(lexeme: &str) -> bool {
+    pub(crate) fn check_name(lexeme: &str) -> bool {
         let mut chars = lexeme.chars();
         chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
             && chars.all(|c| c.is_alphanumeric() || c == '_')
     }
+
+    /// Splits `name[subscript]=value` or `name+=value` into its parts, the subscript being optional.
+    pub(crate) fn split_assignment(lexeme: &str) -> Option<(&str, Option<&str>, bool, &str)> {
+        let end = lexeme
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(lexeme.len());
+
+        let (name, rest) = lexeme.split_at(end);
+
+        if !Self::check_name(name) {
+            return None;
+        }
+
+        let (subscript, rest) = match rest.strip_prefix('[') {
+            Some(rest) => {
+                let close = rest
+                    .match_indices(']')
+                    .map(|(i, _)| i)
+                    .find(|&i| rest[i + 1..].starts_with('=') || rest[i + 1..].starts_with("+="))?;
+
+                (Some(&rest[..close]), &rest[close + 1..])
+            }
+            None => (None, rest),
+        };
+
+        match rest.strip_prefix("+=") {
+            Some(value) => Some((name, subscript, true, value)),
+            None => Some((name, subscript, false, rest.strip_prefix('=')?)),
+        }
+    }
+
+    /// Returns the lexeme of the next token if it is an unquoted blob.
+    fn peek_blob(&mut self) -> Option<&'source str> {
+        let span = self
+            .peek_item()
+            .filter(|t| t.variant == TokenVariant::Blob)?
+            .span
+            .clone();
+
+        self.lexeme(span)
+    }
+
+    /// Makes a word from a bare blob, removing the quotes around any quoted sections in it.
+    fn blob_word(&mut self) -> Option<Word<'source>> {
+        let span = self.next_item()?.span.clone();
+        let lexeme = self.lexeme(span)?;
+
+        if lexeme.contains(['"', '\'']) {
+            self.unquote_current(lexeme)
+        } else {
+            Some(Word::String(lexeme))
+        }
+    }
+
+    /// Whether the next token continues the word ending with the current token, with at most
+    /// quotes between them as in `'a'b` or `"$x"y`.
+    fn continues_word(&mut self) -> bool {
+        let Some(end) = self.current_token_value.as_ref().map(|t| t.span.end) else {
+            return false;
+        };
+
+        let Some(start) = self.peek_item().map(|t| t.span.start) else {
+            return false;
+        };
+
+        self.lexeme(end..start)
+            .is_some_and(|gap| gap.chars().all(|c| c == '"' || c == '\''))
+    }
+
+    /// Joins the parts of a word that follow `first` with nothing but quotes in between.
+    fn rest_of_word(&mut self, first: Word<'source>) -> Option<Word<'source>> {
+        let mut words = vec![first];
+
+        while self.continues_word()
+            && let Some(word) = self.word_part()
+        {
+            words.push(word);
+        }
+
+        match words.len() {
+            1 => words.pop(),
+            _ => Some(Word::Compound { words }),
+        }
+    }
+
+    /// Removes the quotes from `text` taken from the current token, reporting an error against the
+    /// token if a quote is not terminated.
+    fn unquote_current(&mut self, text: &'source str) -> Option<Word<'source>> {
+        let word = Self::unquote(text);
+
+        if word.is_none()
+            && let Some(token) = self.current_token_value.clone()
+        {
+            self.errors.push(SourceError {
+                variant: SourceErrorVariant::Parse(ParseErrorVariant::UnmatchedQuote),
+                lexeme: self.make_string(token.span.clone()).unwrap_or_default(),
+                span: token.span,
+                line: token.line,
+                column: token.column,
+            });
+        }
+
+        word
+    }
+
+    /// Splits a word such as `x'a b'"$y"` into its unquoted and quoted sections, expanding all but
+    /// the single quoted ones. Returns `None` if a quote is not terminated.
+    fn unquote(text: &'source str) -> Option<Word<'source>> {
+        let mut words = Vec::new();
+        let mut rest = text;
+
+        while !rest.is_empty() {
+            let start = rest.find(['"', '\'']).unwrap_or(rest.len());
+
+            if start > 0 {
+                words.push(Self::fragment(&rest[..start]));
+            }
+
+            let Some(quote) = rest[start..].chars().next() else {
+                break;
+            };
+
+            let end = start + 1 + rest[start + 1..].find(quote)?;
+
+            if quote == '\'' {
+                words.push(Word::String(&rest[start + 1..end]));
+            } else {
+                // the section is parsed with its quotes so that the scanner treats it as quoted
+                let section = &rest[start..=end];
+                words.push(Self::parse_word(section).unwrap_or(Word::String(section)));
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        match words.len() {
+            0 => Some(Word::String(text)),
+            1 => words.pop(),
+            _ => Some(Word::Compound { words }),
+        }
+    }
+
+    /// Parses a piece of a word such as an assignment value or subscript, taking the text literally
+    /// if it does not form a single word.
+    fn fragment(text: &'source str) -> Word<'source> {
+        if text.contains(['"', '\'']) {
+            return Self::unquote(text).unwrap_or(Word::String(text));
+        }
+
+        Self::parse_word(text).unwrap_or(Word::String(text))
+    }
+
+    /// Parses `text` as a single word, returning `None` if it is anything else.
+    fn parse_word(text: &'source str) -> Option<Word<'source>> {
+        let mut parser = Parser::new(text);
+
+        parser.word().filter(|_| {
+            parser.errors.is_empty() && parser.check_if(|t| t == TokenVariant::EndOfInput)
+        })
+    }
 }
 
 // parsing methods
 impl<'source> Parser<'source> {
     fn subshell(&mut self) -> Option<Node<'source>> {
-        if self.advance_if(|t| t == TokenVariant::LeftParen) {
-            let node = self.subshell();
+        let node = self.list();
 
-            if !self.advance_if(|t| t == TokenVariant::RightParen) {
-                self.recover(
-                    ParseErrorVariant::UnmatchedParenthesis,
-                    TokenVariant::Newline,
-                );
-            }
-
-            Some(Node::Subshell {
-                node: Box::new(node?),
-            })
-        } else {
-            self.list()
+        if !self.advance_if(|t| t == TokenVariant::RightParen) {
+            self.recover(
+                ParseErrorVariant::UnmatchedParenthesis,
+                TokenVariant::Newline,
+            );
         }
+
+        Some(Node::Subshell {
+            node: Box::new(node?),
+        })
     }
 
     fn list(&mut self) -> Option<Node<'source>> {
         let mut nodes = Vec::new();
+        self.take_until(|t| t != TokenVariant::Newline);
 
         while let Some(node) = self.pipeline() {
+            if self.advance_if(|t| t == TokenVariant::Ampersand) {
+                nodes.push(Node::Background {
+                    node: Box::new(node),
+                });
+
+                self.take_until(|t| t != TokenVariant::Newline);
+                continue;
+            }
+
             nodes.push(node);
 
             if !self.advance_if(|t| t == TokenVariant::Newline || t == TokenVariant::Semicolon) {
                 break;
             }
+
+            self.take_until(|t| t != TokenVariant::Newline);
         }
 
         match nodes.len() {
@@ -225,23 +389,385 @@ impl<'source> Parser<'source> {
     }
 
     fn command(&mut self) -> Option<Node<'source>> {
-        let name = Box::new(self.word()?);
-        let mut args = Vec::new();
+        let mut redirections = Vec::new();
+        let keyword = self.peek_blob();
+
+        let node = if matches!(keyword, Some("do" | "done" | "}")) {
+            // a reserved word closing a compound command ends the list inside it
+            return None;
+        } else if self.advance_if(|t| t == TokenVariant::LeftParen) {
+            let node = self.subshell()?;
+
+            while let Some(redirection) = self.redirection() {
+                redirections.push(redirection);
+            }
+
+            node
+        } else if let Some(keyword @ ("while" | "until" | "for" | "{")) = keyword {
+            self.next_item();
+
+            let node = match keyword {
+                "for" => self.for_loop()?,
+                "{" => self.group()?,
+                keyword => self.while_loop(keyword == "until")?,
+            };
+
+            while let Some(redirection) = self.redirection() {
+                redirections.push(redirection);
+            }
+
+            node
+        } else if self.keyword("function") {
+            let Some(name) = self.peek_blob().filter(|name| Self::check_name(name)) else {
+                self.recover(ParseErrorVariant::InvalidName, TokenVariant::Newline);
+                return None;
+            };
+
+            self.next_item();
+
+            if self.advance_if(|t| t == TokenVariant::LeftParen) {
+                self.function_parens()?;
+            }
+
+            return self.function(name);
+        } else {
+            let mut assignments = Vec::new();
+            let mut words = Vec::new();
+
+            while let Some(assignment) = self.assignment() {
+                assignments.push(assignment);
+            }
+
+            loop {
+                if let Some(redirection) = self.redirection() {
+                    redirections.push(redirection);
+                } else if let Some(Word::String("declare" | "typeset" | "local")) = words.first()
+                    && let Some(word) = self.declaration()
+                {
+                    words.push(word);
+                } else if let Some(word) = self.word() {
+                    words.push(word);
+                } else {
+                    break;
+                }
+            }
+
+            if let [Word::String(name)] = words[..]
+                && assignments.is_empty()
+                && redirections.is_empty()
+                && Self::check_name(name)
+                && self.advance_if(|t| t == TokenVariant::LeftParen)
+            {
+                self.function_parens()?;
+                return self.function(name);
+            }
+
+            if !words.is_empty() && !assignments.is_empty() {
+                // assignments preceding a command are not supported, so they remain plain words
+                let mut prefix = Vec::new();
+
+                for (assignment, lexeme) in assignments.drain(..) {
+                    if let AssignmentValue::Array(_) = assignment.value {
+                        self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
+                        return None;
+                    }
+
+                    prefix.push(Word::String(lexeme));
+                }
+
+                words.splice(0..0, prefix);
+            }
+
+            let mut words = words.into_iter();
+
+            match words.next() {
+                Some(name) => Node::Command {
+                    command: Command {
+                        name: Box::new(name),
+                        args: words.collect(),
+                    },
+                },
+                None if !assignments.is_empty() => Node::Assignment {
+                    assignments: assignments.into_iter().map(|(a, _)| a).collect(),
+                },
+                None if redirections.is_empty() => return None,
+                None => Node::List { nodes: Vec::new() },
+            }
+        };
+
+        if redirections.is_empty() {
+            Some(node)
+        } else {
+            Some(Node::Redirection {
+                redirections,
+                node: Box::new(node),
+            })
+        }
+    }
+
+    /// Parses `while list; do list; done`, or its `until` form, after the first keyword.
+    fn while_loop(&mut self, until: bool) -> Option<Node<'source>> {
+        let Some(predicate) = self.list() else {
+            self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
+            return None;
+        };
 
-        while let Some(arg) = self.word() {
-            args.push(arg);
+        let predicate = Box::new(predicate);
+        let body = Box::new(self.loop_body()?);
+
+        Some(if until {
+            Node::Until { predicate, body }
+        } else {
+            Node::While { predicate, body }
+        })
+    }
+
+    /// Parses `for name [in words]; do list; done` after the `for` keyword.
+    fn for_loop(&mut self) -> Option<Node<'source>> {
+        let Some(name) = self.peek_blob().filter(|name| Self::check_name(name)) else {
+            self.recover(ParseErrorVariant::InvalidName, TokenVariant::Newline);
+            return None;
+        };
+
+        self.next_item();
+        self.take_until(|t| t != TokenVariant::Newline);
+
+        let words = if self.keyword("in") {
+            let mut words = Vec::new();
+
+            while let Some(word) = self.word() {
+                words.push(word);
+            }
+
+            Some(words)
+        } else {
+            None
+        };
+
+        self.advance_if(|t| t == TokenVariant::Semicolon || t == TokenVariant::Newline);
+
+        Some(Node::For {
+            name,
+            words,
+            body: Box::new(self.loop_body()?),
+        })
+    }
+
+    /// Parses the `do list; done` ending a loop.
+    fn loop_body(&mut self) -> Option<Node<'source>> {
+        self.take_until(|t| t != TokenVariant::Newline);
+
+        let body = if self.keyword("do") {
+            self.list()
+        } else {
+            None
+        };
+
+        if body.is_none() || !self.keyword("done") {
+            self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
+            return None;
+        }
+
+        body
+    }
+
+    /// Parses `{ list; }` after the opening brace.
+    fn group(&mut self) -> Option<Node<'source>> {
+        let node = self.list();
+
+        if node.is_none() || !self.keyword("}") {
+            self.recover(ParseErrorVariant::UnmatchedBrace, TokenVariant::Newline);
+            return None;
+        }
+
+        Some(Node::Group {
+            node: Box::new(node?),
+        })
+    }
+
+    /// Takes the closing parenthesis of the `()` after a function name.
+    fn function_parens(&mut self) -> Option<()> {
+        if self.advance_if(|t| t == TokenVariant::RightParen) {
+            Some(())
+        } else {
+            self.recover(
+                ParseErrorVariant::UnmatchedParenthesis,
+                TokenVariant::Newline,
+            );
+            None
+        }
+    }
+
+    /// Parses the compound command making up the body of the function `name`.
+    fn function(&mut self, name: &'source str) -> Option<Node<'source>> {
+        self.take_until(|t| t != TokenVariant::Newline);
+
+        let start = self.peek_item()?.span.start;
+        let compound = matches!(self.peek_blob(), Some("{" | "while" | "until" | "for"))
+            || self.check_if(|t| t == TokenVariant::LeftParen);
+
+        if !compound || self.command().is_none() {
+            self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
+            return None;
+        }
+
+        let end = self.current_token_value.as_ref()?.span.end;
+
+        Some(Node::Function {
+            name,
+            body: self.lexeme(start..end)?,
+        })
+    }
+
+    /// Takes the next token if it is the unquoted reserved word `keyword`.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_blob() == Some(keyword);
+
+        if found {
+            self.next_item();
+        }
+
+        found
+    }
+
+    fn assignment(&mut self) -> Option<(Assignment<'source>, &'source str)> {
+        let lexeme = self.peek_blob()?;
+        let (name, subscript, append, value) = Self::split_assignment(lexeme)?;
+        let end = self.next_item()?.span.end;
+        let value = self.assignment_value(value, subscript.is_some(), end)?;
+
+        let assignment = Assignment {
+            name,
+            subscript: subscript.map(Self::fragment),
+            append,
+            value,
+        };
+
+        Some((assignment, lexeme))
+    }
+
+    /// Parses an assignment argument of a declaration builtin such as `local name=value`, so that
+    /// the value is expanded as in an assignment. Arrays are kept as source text for the builtin.
+    fn declaration(&mut self) -> Option<Word<'source>> {
+        let lexeme = self.peek_blob()?;
+        let (_, subscript, _, value) = Self::split_assignment(lexeme)?;
+        let span = self.next_item()?.span.clone();
+        let prefix = Word::String(&lexeme[..lexeme.len() - value.len()]);
+
+        match self.assignment_value(value, subscript.is_some(), span.end)? {
+            AssignmentValue::Scalar(word) => Some(Word::Compound {
+                words: vec![prefix, word],
+            }),
+            AssignmentValue::Array(_) => {
+                let end = self.current_token_value.as_ref()?.span.end;
+                self.make_word(span.start..end)
+            }
+        }
+    }
+
+    /// Parses the value of an assignment word ending at `end`, including array values, which the
+    /// scanner splits off at the opening parenthesis, and any parts of the value following it.
+    fn assignment_value(
+        &mut self,
+        value: &'source str,
+        subscript: bool,
+        end: usize,
+    ) -> Option<AssignmentValue<'source>> {
+        let paren = self.check_if(|t| t == TokenVariant::LeftParen)
+            && self.peek_item().is_some_and(|t| t.span.start == end);
+
+        if paren && value.is_empty() && !subscript {
+            self.next_item();
+            return Some(AssignmentValue::Array(self.array()?));
+        }
+
+        let first = self.scalar_value(value)?;
+        Some(AssignmentValue::Scalar(self.rest_of_word(first)?))
+    }
+
+    /// Expands the text of a scalar assignment value taken from the current token.
+    fn scalar_value(&mut self, value: &'source str) -> Option<Word<'source>> {
+        if value.contains(['"', '\'']) {
+            self.unquote_current(value)
+        } else {
+            Some(Self::fragment(value))
+        }
+    }
+
+    fn array(&mut self) -> Option<Vec<(Option<Word<'source>>, Word<'source>)>> {
+        let mut elements = Vec::new();
+
+        loop {
+            self.take_until(|t| t != TokenVariant::Newline);
+
+            if self.advance_if(|t| t == TokenVariant::RightParen) {
+                return Some(elements);
+            }
+
+            if let Some(lexeme) = self.peek_blob()
+                && let Some(rest) = lexeme.strip_prefix('[')
+                && let Some((key, value)) = rest.split_once("]=")
+            {
+                self.next_item();
+                let value = self.rest_of_word(Self::fragment(value))?;
+                elements.push((Some(Self::fragment(key)), value));
+            } else if let Some(word) = self.word() {
+                elements.push((None, word));
+            } else {
+                self.recover(
+                    ParseErrorVariant::UnmatchedParenthesis,
+                    TokenVariant::Newline,
+                );
+
+                return None;
+            }
         }
+    }
 
-        Some(Node::Command {
-            command: Command { name, args },
+    fn redirection(&mut self) -> Option<Redirection<'source>> {
+        let fd = if let Some(token) = self.next_if(|t| t == TokenVariant::IoNumber) {
+            let span = token.span.clone();
+            self.lexeme(span)?.parse().ok()
+        } else {
+            None
+        };
+
+        let operator = match self.peek_item()?.variant {
+            TokenVariant::Less => RedirectionOperator::Input,
+            TokenVariant::Greater => RedirectionOperator::Output,
+            TokenVariant::GreaterGreater => RedirectionOperator::Append,
+            TokenVariant::GreaterBar => RedirectionOperator::Clobber,
+            TokenVariant::LessGreater => RedirectionOperator::ReadWrite,
+            TokenVariant::LessAmper => RedirectionOperator::DupInput,
+            TokenVariant::GreaterAmper => RedirectionOperator::DupOutput,
+            _ => return None,
+        };
+
+        self.next_item();
+
+        let Some(target) = self.word() else {
+            self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
+            return None;
+        };
+
+        Some(Redirection {
+            fd,
+            operator,
+            target,
         })
     }
 
     fn word(&mut self) -> Option<Word<'source>> {
+        let first = self.word_part()?;
+        self.rest_of_word(first)
+    }
+
+    /// Parses one part of a word: a bare or quoted string, a parameter or a command substitution.
+    fn word_part(&mut self) -> Option<Word<'source>> {
         if self.advance_if(|t| t == TokenVariant::Dollar) {
-            self.parameter()
+            self.parameter(false)
         } else if self.advance_if(|t| t == TokenVariant::DollarLeftBrace) {
-            let word = self.parameter();
+            let word = self.parameter(true);
 
             if !self.advance_if(|t| t == TokenVariant::RightBrace) {
                 self.recover(ParseErrorVariant::UnmatchedBrace, TokenVariant::Newline);
@@ -249,7 +775,7 @@ impl<'source> Parser<'source> {
 
             word
         } else if self.advance_if(|t| t == TokenVariant::DollarLeftParen) {
-            let node = self.subshell()?;
+            let node = self.list()?;
 
             if !self.advance_if(|t| t == TokenVariant::RightParen) {
                 self.recover(
@@ -282,23 +808,80 @@ impl<'source> Parser<'source> {
                 1 => words.into_iter().next(),
                 _ => Some(Word::Compound { words }),
             }
-        } else {
-            let span = self.next_if(|t| t == TokenVariant::Blob)?.span.clone();
+        } else if self.check_if(|t| t == TokenVariant::Blob) {
+            self.blob_word()
+        } else if let Some(token) = self.next_if(|t| t == TokenVariant::Quoted) {
+            let span = token.span.clone();
             self.make_word(span)
+        } else {
+            None
         }
     }
 
-    fn parameter(&mut self) -> Option<Word<'source>> {
+    fn parameter(&mut self, braced: bool) -> Option<Word<'source>> {
         let span = self.next_if(|t| t == TokenVariant::Blob)?.span.clone();
         let lexeme = self.lexeme(span)?;
 
+        let parameter = if braced {
+            Self::braced_parameter(lexeme)
+        } else {
+            Self::simple_parameter(lexeme)
+        };
+
+        match parameter {
+            Some(parameter) => Some(Word::Parameter(parameter)),
+            None => {
+                self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
+                None
+            }
+        }
+    }
+
+    fn simple_parameter(lexeme: &'source str) -> Option<Parameter<'source>> {
         if let Ok(n) = lexeme.parse::<usize>() {
-            Some(Word::Parameter(Parameter::Number(n)))
+            Some(Parameter::Number(n))
+        } else if lexeme == "?" {
+            Some(Parameter::Status)
+        } else if lexeme == "-" {
+            Some(Parameter::Flags)
         } else if Self::check_name(lexeme) {
-            Some(Word::Parameter(Parameter::String(lexeme)))
+            Some(Parameter::String(lexeme))
         } else {
-            self.recover(ParseErrorVariant::UnexpectedTokens, TokenVariant::Newline);
             None
         }
     }
+
+    fn braced_parameter(lexeme: &'source str) -> Option<Parameter<'source>> {
+        if let Some(name) = lexeme
+            .strip_prefix('!')
+            .and_then(|rest| rest.strip_suffix("[@]").or(rest.strip_suffix("[*]")))
+            && Self::check_name(name)
+        {
+            return Some(Parameter::Keys(name));
+        }
+
+        if let Some(rest) = lexeme.strip_prefix('#')
+            && !rest.is_empty()
+        {
+            return match Self::braced_parameter(rest)? {
+                Parameter::Length(_) | Parameter::Keys(_) => None,
+                parameter => Some(Parameter::Length(Box::new(parameter))),
+            };
+        }
+
+        if let Some((name, rest)) = lexeme.split_once('[')
+            && let Some(subscript) = rest.strip_suffix(']')
+            && Self::check_name(name)
+        {
+            let subscript = match subscript {
+                "@" => Subscript::At,
+                "*" => Subscript::Star,
+                index => Subscript::Index(Box::new(Self::fragment(index))),
+            };
+
+            return Some(Parameter::Element { name, subscript });
+        }
+
+        Self::simple_parameter(lexeme)
+    }
 }
@@ -9,6 +9,7 @@ enum ScanMode {
     BackQuotes,
     ReserveWord,
     Braces,
+    Parameter,
     FunctionBody, // todo
     Assignment,   // todo
 }
@@ -52,6 +53,7 @@ impl<'source> Scanner<'source> {
             Some(ScanMode::DoubleQuotes) => self.double_quotes(),
             Some(ScanMode::BackQuotes) => self.back_quotes(),
             Some(ScanMode::Braces) => self.braces(),
+            Some(ScanMode::Parameter) => self.parameter(),
             _ => self.root(),
         }
     }
@@ -121,13 +123,19 @@ impl Scanner<'_> {
         ("!", TokenVariant::Bang),
     ];
 
+    pub(crate) fn is_reserved_word(word: &str) -> bool {
+        Self::RESERVED_WORDS
+            .iter()
+            .any(|(reserved, _)| *reserved == word)
+    }
+
     const META_CHARS: &'static str = "|&;()<>";
     const DOUBLE_QUOTED_CHARS: &'static str = "$`\"\\";
 
     fn delimit_token(&mut self, variant: TokenVariant) -> Token {
         let span = self.token_start..self.token_end;
         let line = self.line;
-        let column = self.column + self.token_start - self.token_end;
+        let column = (self.column + self.token_start).saturating_sub(self.token_end);
         self.token_start = self.token_end;
 
         Token {
@@ -147,6 +155,11 @@ impl Scanner<'_> {
                 '"' => {
                     self.token_start = self.token_end;
                     self.mode_stack.push(ScanMode::DoubleQuotes);
+
+                    if self.check_if(|c| c == '"') {
+                        return self.delimit_token(TokenVariant::Quoted);
+                    }
+
                     return self.double_quotes();
                 }
                 '(' => {
@@ -169,6 +182,8 @@ impl Scanner<'_> {
                 '$' => return self.dollar(),
                 '&' => return self.ampersand(),
                 '|' => return self.bar(),
+                '<' => return self.less(),
+                '>' => return self.greater(),
                 c if c.is_whitespace() => {
                     self.token_start = self.token_end;
                     continue;
@@ -194,13 +209,9 @@ impl Scanner<'_> {
                     return self.delimit_token(TokenVariant::BackQuote);
                 }
                 '\\' => todo!("implement character escapes"),
-                c if c.is_whitespace() => {
-                    self.token_start = self.token_end;
-                    continue;
-                }
                 _ => {
                     self.take_until(|c| Self::DOUBLE_QUOTED_CHARS.contains(c));
-                    return self.delimit_token(TokenVariant::Blob);
+                    return self.delimit_token(TokenVariant::Quoted);
                 }
             }
         }
@@ -218,8 +229,9 @@ impl Scanner<'_> {
     fn single_quotes(&mut self) -> Token {
         self.token_start = self.token_end;
         self.take_until(|c| c == '\'');
-        let token = self.delimit_token(TokenVariant::Blob);
+        let token = self.delimit_token(TokenVariant::Quoted);
         self.next_item();
+        self.token_start = self.token_end;
         token
     }
 
@@ -236,11 +248,30 @@ impl Scanner<'_> {
         } else if self.advance_if(|c| c == '(') {
             self.mode_stack.push(ScanMode::Subshell);
             self.delimit_token(TokenVariant::DollarLeftParen)
-        } else {
+        } else if self.check_if(|c| c.is_alphanumeric() || "_?-#@*!$".contains(c)) {
+            self.mode_stack.push(ScanMode::Parameter);
             self.delimit_token(TokenVariant::Dollar)
+        } else if self.mode_stack.last() == Some(&ScanMode::DoubleQuotes) {
+            self.delimit_token(TokenVariant::Quoted)
+        } else {
+            self.delimit_token(TokenVariant::Blob)
         }
     }
 
+    /// Scans the name after a `$`: a run of name characters, or a single digit or special
+    /// character.
+    fn parameter(&mut self) -> Token {
+        self.mode_stack.pop();
+
+        if self.advance_if(|c| c.is_alphabetic() || c == '_') {
+            self.take_until(|c| !(c.is_alphanumeric() || c == '_'));
+        } else {
+            self.next_item();
+        }
+
+        self.delimit_token(TokenVariant::Blob)
+    }
+
     fn braces(&mut self) -> Token {
         if self.advance_if(|c| c == '}') {
             self.mode_stack.pop();
@@ -267,13 +298,70 @@ impl Scanner<'_> {
         }
     }
 
+    fn less(&mut self) -> Token {
+        if self.advance_if(|c| c == '<') {
+            self.delimit_token(TokenVariant::LessLess)
+        } else if self.advance_if(|c| c == '&') {
+            self.delimit_token(TokenVariant::LessAmper)
+        } else if self.advance_if(|c| c == '>') {
+            self.delimit_token(TokenVariant::LessGreater)
+        } else {
+            self.delimit_token(TokenVariant::Less)
+        }
+    }
+
+    fn greater(&mut self) -> Token {
+        if self.advance_if(|c| c == '>') {
+            self.delimit_token(TokenVariant::GreaterGreater)
+        } else if self.advance_if(|c| c == '&') {
+            self.delimit_token(TokenVariant::GreaterAmper)
+        } else if self.advance_if(|c| c == '|') {
+            self.delimit_token(TokenVariant::GreaterBar)
+        } else {
+            self.delimit_token(TokenVariant::Greater)
+        }
+    }
+
     fn comment(&mut self) {
         self.take_until(|c| c == '\n');
         self.token_start = self.token_end;
     }
 
+    /// Scans an unquoted word, keeping any quoted sections inside it so that `name='a b'` stays
+    /// a single blob. The parser removes the quotes. The blob ends before a `$`, which the parser
+    /// joins back onto the word, unless it is inside a subscript such as `a[$i]=x`.
     fn blob(&mut self) -> Token {
-        self.take_until(|c| c.is_whitespace() || Self::META_CHARS.contains(c));
+        let mut brackets = usize::from(self.current_char_value == Some('['));
+
+        while let Some(c) = self.peek_item().copied() {
+            if c.is_whitespace() || Self::META_CHARS.contains(c) || (c == '$' && brackets == 0) {
+                break;
+            }
+
+            self.next_item();
+
+            match c {
+                '[' => brackets += 1,
+                ']' => brackets = brackets.saturating_sub(1),
+                '\'' | '"' => {
+                    self.take_until(|d| d == c);
+
+                    if !self.advance_if(|d| d == c) {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let is_number = self
+            .source
+            .get(self.token_start..self.token_end)
+            .is_some_and(|lexeme| lexeme.chars().all(|c| c.is_ascii_digit()));
+
+        if is_number && self.check_if(|c| c == '<' || c == '>') {
+            return self.delimit_token(TokenVariant::IoNumber);
+        }
 
         if self.mode_stack.last() == Some(&ScanMode::ReserveWord)
             && let Some(token) = self.reserved_word()
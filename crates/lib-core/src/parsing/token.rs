@@ -37,6 +37,10 @@ pub enum TokenVariant {
     Greater,
     GreaterEqual,
     GreaterGreater,
+    LessAmper,
+    GreaterAmper,
+    LessGreater,
+    GreaterBar,
     SingleQuote,
     DoubleQuote,
     BackQuote,
@@ -46,7 +50,9 @@ pub enum TokenVariant {
     Parameter,
     Name,
     Blob,
+    Quoted,
     Number,
+    IoNumber,
 
     // reserved words
     Function,
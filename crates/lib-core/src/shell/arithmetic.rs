@@ -0,0 +1,383 @@
+/// Maximum depth of variables referring to further expressions before evaluation gives up.
+const MAX_DEPTH: usize = 64;
+
+/// Evaluates an integer arithmetic expression as for `declare -i` and array subscripts, looking up
+/// variables through `lookup`. Variables holding expressions are evaluated in turn, and unset or
+/// empty variables are zero.
+pub(crate) fn evaluate(
+    expression: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<i64, String> {
+    evaluate_at(expression, lookup, 0)
+}
+
+fn evaluate_at(
+    expression: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    depth: usize,
+) -> Result<i64, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("{expression}: expression recursion level exceeded"));
+    }
+
+    let mut parser = Arithmetic {
+        expression,
+        position: 0,
+        lookup,
+        depth,
+        skip: false,
+    };
+
+    parser.skip_whitespace();
+
+    if parser.at_end() {
+        return Ok(0);
+    }
+
+    let value = parser.ternary()?;
+    parser.skip_whitespace();
+
+    match parser.at_end() {
+        true => Ok(value),
+        false => Err(parser.error("syntax error in expression")),
+    }
+}
+
+struct Arithmetic<'a> {
+    expression: &'a str,
+    position: usize,
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    depth: usize,
+    /// Set while parsing an operand whose value cannot affect the result, such as the right side
+    /// of `0 && x`, which is checked for syntax but neither looks up variables nor fails.
+    skip: bool,
+}
+
+/// Binary operators from loosest to tightest binding, excluding the ternary and exponentiation.
+const LEVELS: [&[&str]; 10] = [
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<=", ">=", "<", ">"],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+impl Arithmetic<'_> {
+    fn rest(&self) -> &str {
+        &self.expression[self.position..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!(
+            "{}: {message} (error token is \"{}\")",
+            self.expression,
+            self.rest()
+        )
+    }
+
+    /// Consumes `op` if it is next and is not the start of a longer operator.
+    fn operator(&mut self, op: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+
+        let longer = match op {
+            "|" => rest.starts_with("||"),
+            "&" => rest.starts_with("&&"),
+            "<" => rest.starts_with("<<") || rest.starts_with("<="),
+            ">" => rest.starts_with(">>") || rest.starts_with(">="),
+            "*" => rest.starts_with("**"),
+            "!" => rest.starts_with("!="),
+            _ => false,
+        };
+
+        if rest.starts_with(op) && !longer {
+            self.position += op.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Runs `f` without evaluating anything if `skip` is set, restoring the previous state after.
+    fn skipping(
+        &mut self,
+        skip: bool,
+        f: impl FnOnce(&mut Self) -> Result<i64, String>,
+    ) -> Result<i64, String> {
+        let previous = self.skip;
+        self.skip |= skip;
+        let result = f(self);
+        self.skip = previous;
+        result
+    }
+
+    fn ternary(&mut self) -> Result<i64, String> {
+        let condition = self.binary(0)?;
+
+        if !self.operator("?") {
+            return Ok(condition);
+        }
+
+        let left = self.skipping(condition == 0, Self::ternary)?;
+
+        if !self.operator(":") {
+            return Err(self.error("`:' expected for conditional expression"));
+        }
+
+        let right = self.skipping(condition != 0, Self::ternary)?;
+        Ok(if condition != 0 { left } else { right })
+    }
+
+    fn binary(&mut self, level: usize) -> Result<i64, String> {
+        let Some(operators) = LEVELS.get(level) else {
+            return self.power();
+        };
+
+        let mut left = self.binary(level + 1)?;
+
+        'operators: loop {
+            for op in operators.iter() {
+                if self.operator(op) {
+                    let decided = match *op {
+                        "&&" => left == 0,
+                        "||" => left != 0,
+                        _ => false,
+                    };
+
+                    let right = self.skipping(decided, |parser| parser.binary(level + 1))?;
+                    left = self.apply(op, left, right)?;
+                    continue 'operators;
+                }
+            }
+
+            return Ok(left);
+        }
+    }
+
+    fn apply(&self, op: &str, left: i64, right: i64) -> Result<i64, String> {
+        Ok(match op {
+            "||" => (left != 0 || right != 0) as i64,
+            "&&" => (left != 0 && right != 0) as i64,
+            "|" => left | right,
+            "^" => left ^ right,
+            "&" => left & right,
+            "==" => (left == right) as i64,
+            "!=" => (left != right) as i64,
+            "<=" => (left <= right) as i64,
+            ">=" => (left >= right) as i64,
+            "<" => (left < right) as i64,
+            ">" => (left > right) as i64,
+            "<<" => left.wrapping_shl(right as u32),
+            ">>" => left.wrapping_shr(right as u32),
+            "+" => left.wrapping_add(right),
+            "-" => left.wrapping_sub(right),
+            "*" => left.wrapping_mul(right),
+            "/" | "%" if right == 0 && self.skip => 0,
+            "/" | "%" if right == 0 => return Err(self.error("division by 0")),
+            "/" => left.wrapping_div(right),
+            _ => left.wrapping_rem(right),
+        })
+    }
+
+    fn power(&mut self) -> Result<i64, String> {
+        let base = self.unary()?;
+
+        if !self.operator("**") {
+            return Ok(base);
+        }
+
+        match self.power()? {
+            _ if self.skip => Ok(0),
+            exponent if exponent < 0 => Err(self.error("exponent less than 0")),
+            exponent => Ok(base.wrapping_pow(exponent as u32)),
+        }
+    }
+
+    fn unary(&mut self) -> Result<i64, String> {
+        if self.operator("!") {
+            Ok((self.unary()? == 0) as i64)
+        } else if self.operator("~") {
+            Ok(!self.unary()?)
+        } else if self.operator("-") {
+            Ok(self.unary()?.wrapping_neg())
+        } else if self.operator("+") {
+            self.unary()
+        } else {
+            self.operand()
+        }
+    }
+
+    fn operand(&mut self) -> Result<i64, String> {
+        self.skip_whitespace();
+
+        if self.operator("(") {
+            let value = self.ternary()?;
+
+            return match self.operator(")") {
+                true => Ok(value),
+                false => Err(self.error("missing `)'")),
+            };
+        }
+
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '#'))
+            .unwrap_or(rest.len());
+
+        let token = &rest[..len];
+
+        if token.is_empty() {
+            return Err(self.error("syntax error: operand expected"));
+        }
+
+        let value = if token.starts_with(|c: char| c.is_ascii_digit()) {
+            self.number(token)?
+        } else if !token.contains('#') && self.skip {
+            0
+        } else if !token.contains('#') {
+            match (self.lookup)(token) {
+                Some(value) if !value.trim().is_empty() => {
+                    evaluate_at(&value, self.lookup, self.depth + 1)?
+                }
+                _ => 0,
+            }
+        } else {
+            return Err(self.error("syntax error: operand expected"));
+        };
+
+        self.position += len;
+        Ok(value)
+    }
+
+    /// Parses decimal, `0x` hexadecimal, leading zero octal and `base#digits` constants.
+    fn number(&self, token: &str) -> Result<i64, String> {
+        let (base, digits) = if let Some((base, digits)) = token.split_once('#') {
+            match base.parse::<u32>() {
+                Ok(base @ 2..=64) => (base, digits),
+                _ => return Err(self.error("invalid arithmetic base")),
+            }
+        } else if let Some(digits) = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+        {
+            (16, digits)
+        } else if token.len() > 1
+            && let Some(digits) = token.strip_prefix('0')
+        {
+            (8, digits)
+        } else {
+            (10, token)
+        };
+
+        let mut value: i64 = 0;
+
+        for c in digits.chars() {
+            let digit = match c {
+                '0'..='9' => c as u32 - '0' as u32,
+                'a'..='z' => c as u32 - 'a' as u32 + 10,
+                'A'..='Z' if base <= 36 => c as u32 - 'A' as u32 + 10,
+                'A'..='Z' => c as u32 - 'A' as u32 + 36,
+                '@' => 62,
+                '_' => 63,
+                _ => u32::MAX,
+            };
+
+            if digit >= base {
+                return Err(self.error("value too great for base"));
+            }
+
+            value = value.wrapping_mul(base as i64).wrapping_add(digit as i64);
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate;
+
+    fn eval(expression: &str) -> Result<i64, String> {
+        evaluate(expression, &|name| match name {
+            "x" => Some(String::from("6")),
+            "y" => Some(String::from("x * 2")),
+            "loop" => Some(String::from("loop + 1")),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9));
+        assert_eq!(eval("2 ** 3 ** 2"), Ok(512));
+        assert_eq!(eval("-2 ** 2"), Ok(4));
+        assert_eq!(eval("1 + 2 == 3 && 4 < 5"), Ok(1));
+        assert_eq!(eval("1 | 2 ^ 3 & 4"), Ok(3));
+        assert_eq!(eval("1 << 2 + 1"), Ok(8));
+        assert_eq!(eval("0 ? 1 : 2 ? 3 : 4"), Ok(3));
+        assert_eq!(eval("!0 + ~0"), Ok(0));
+        assert_eq!(eval(""), Ok(0));
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(eval("x + 1"), Ok(7));
+        assert_eq!(eval("y - x"), Ok(6));
+        assert_eq!(eval("unset + 1"), Ok(1));
+    }
+
+    #[test]
+    fn bases() {
+        assert_eq!(eval("0x1F"), Ok(31));
+        assert_eq!(eval("017"), Ok(15));
+        assert_eq!(eval("2#101"), Ok(5));
+        assert_eq!(eval("36#z"), Ok(35));
+        assert_eq!(eval("64#_"), Ok(63));
+        assert!(eval("08").is_err());
+        assert!(eval("65#1").is_err());
+    }
+
+    #[test]
+    fn short_circuit() {
+        assert_eq!(eval("0 && 1 / 0"), Ok(0));
+        assert_eq!(eval("1 || 1 / 0"), Ok(1));
+        assert_eq!(eval("1 ? 2 : 1 / 0"), Ok(2));
+        assert_eq!(eval("0 ? 1 / 0 : 3"), Ok(3));
+        assert_eq!(eval("0 && loop"), Ok(0));
+        assert!(eval("1 && 1 / 0").is_err());
+        assert!(eval("0 && (1").is_err());
+    }
+
+    #[test]
+    fn recursion_limit() {
+        let error = eval("loop").unwrap_err();
+        assert!(
+            error.contains("expression recursion level exceeded"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let error = eval("5 / (x - 6)").unwrap_err();
+        assert!(error.contains("division by 0"), "{error}");
+        assert!(eval("5 % 0").is_err());
+        assert_eq!(eval("7 / 2"), Ok(3));
+        assert_eq!(eval("-7 % 3"), Ok(-1));
+    }
+}
@@ -1,32 +1,102 @@
-use std::io::Read;
-
-use sysexits::ExitCode;
+use std::{
+    fs::{File, OpenOptions},
+    io::{ErrorKind, Read},
+    os::fd::{AsRawFd, RawFd},
+    path::{Path, PathBuf},
+};
 
-use lib_os::{dir, io};
+use lib_os::{
+    dir, io,
+    process::{self, Fork, Pid},
+};
 
 use crate::{
+    ExitStatus, Result, Shell,
     builtin::Builtin,
-    parsing::{Command, Node, Parameter, Redirection, Word},
-    Result, Shell,
+    error::Error,
+    parsing::{
+        Assignment, AssignmentValue, Command, Node, Parameter, Parser, Redirection,
+        RedirectionOperator, Subscript, Word,
+    },
+    shell::{Flow, Job, JobState, JobTable, ShellOption, Trap, Value},
 };
 
+enum Launch {
+    Builtin(ExitStatus),
+    Process(Pid),
+}
+
+enum Stage {
+    Done(ExitStatus),
+    Process,
+}
+
+/// How a loop carries on after running part of its body.
+enum Step {
+    Next(ExitStatus),
+    Continue,
+    Break,
+}
+
 impl Shell {
-    pub fn execute(&mut self, ctx: Option<io::Context>, node: &Node) -> Result<ExitCode> {
-        if self.should_exit {
+    pub fn execute(&mut self, ctx: Option<io::Context>, node: &Node) -> Result<ExitStatus> {
+        if self.options.get(ShellOption::Noexec) && !self.interactive {
             return Ok(self.exit_code);
         }
 
-        match node {
-            Node::Command {
-                command: Command { name, args },
-            } => self.command(ctx, name, args),
+        self.run_pending_traps();
+        self.check_flow()?;
+
+        let code = match node {
+            Node::Command { command } => self.command(ctx, command),
+            Node::Assignment { assignments } => self.assignment(assignments),
             Node::List { nodes } => self.list(ctx, nodes),
             Node::Pipeline { nodes } => self.pipeline(ctx, nodes),
             Node::Or { left, right } => self.logical(ctx, false, left, right),
             Node::And { left, right } => self.logical(ctx, true, left, right),
-            Node::Subshell { node } => self.execute(ctx, node),
+            Node::Subshell { node } => self.subshell(ctx, node),
+            Node::Background { node } => self.background(ctx, node),
             Node::Redirection { redirections, node } => self.redirection(ctx, redirections, node),
+            Node::While { predicate, body } => self.while_loop(ctx, false, predicate, body),
+            Node::Until { predicate, body } => self.while_loop(ctx, true, predicate, body),
+            Node::For { name, words, body } => self.for_loop(ctx, name, words.as_deref(), body),
+            Node::Group { node } => self.execute(ctx, node),
+            Node::Function { name, body } => {
+                self.functions.insert(name.to_string(), body.to_string());
+                Ok(ExitStatus::SUCCESS)
+            }
             _ => todo!(),
+        }?;
+
+        self.exit_code = code;
+        self.run_pending_traps();
+        self.check_flow()?;
+
+        if !code.is_success()
+            && self.errexit_exempt == 0
+            && matches!(
+                node,
+                Node::Command { .. }
+                    | Node::Assignment { .. }
+                    | Node::Pipeline { .. }
+                    | Node::Subshell { .. }
+            )
+        {
+            self.run_trap(Trap::Err);
+            self.check_flow()?;
+
+            if self.options.get(ShellOption::Errexit) {
+                return Err(Error::Flow(Flow::Exit(code)));
+            }
+        }
+
+        Ok(code)
+    }
+
+    fn check_flow(&mut self) -> Result<()> {
+        match self.flow.take() {
+            Some(flow) => Err(Error::Flow(flow)),
+            None => Ok(()),
         }
     }
 
@@ -37,16 +107,23 @@ impl Shell {
             Word::Command { node } => {
                 let (mut reader, writer) = io::pipe()?;
 
-                let ctx = io::Context {
-                    input: self.io.input.try_clone()?,
-                    output: writer.into(),
-                    error: self.io.error.try_clone()?,
-                };
+                let mut ctx = self.io.try_clone()?;
+                ctx.set(1, writer);
+
+                // the substitution stays in the shell's process group and leaves it the terminal
+                let pgid = process::shell_group();
+                let pid = self.fork(pgid, false, |shell| shell.execute(Some(ctx), node))?;
+                let mut buffer = Vec::new();
+                let result = reader.read_to_end(&mut buffer);
 
-                self.execute(Some(ctx), node)?;
-                let mut output = String::new();
-                reader.read_to_string(&mut output)?;
-                Ok(output)
+                let mut job = Job::new(word.to_string());
+                job.pgid = pgid;
+                job.push(pid);
+                self.exit_code = self.foreground(job)?;
+                result?;
+
+                let output = String::from_utf8_lossy(&buffer);
+                Ok(output.trim_end_matches('\n').to_string())
             }
             Word::Compound { words } => {
                 let strings = words
@@ -59,81 +136,648 @@ impl Shell {
         }
     }
 
+    /// Expands a word into fields, giving one per element for `${name[@]}` and `${!name[@]}`.
+    fn fields(&mut self, word: &Word) -> Result<Vec<String>> {
+        match word {
+            Word::Parameter(Parameter::Element {
+                name,
+                subscript: Subscript::At,
+            }) => Ok(self.elements(name)),
+            Word::Parameter(Parameter::Keys(name)) => Ok(self.keys(name)),
+            word => Ok(vec![self.word(word)?]),
+        }
+    }
+
+    fn array(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)?.value.as_ref()
+    }
+
+    fn elements(&self, name: &str) -> Vec<String> {
+        self.array(name)
+            .map(|array| array.values().into_iter().map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    fn keys(&self, name: &str) -> Vec<String> {
+        self.array(name).map(Value::keys).unwrap_or_default()
+    }
+
     fn parameter(&mut self, p: &Parameter) -> Result<String> {
         Ok(match p {
-            Parameter::String(s) => std::env::var(s).unwrap_or_default(),
-            Parameter::Number(n) => self.args.get(*n).cloned().unwrap_or_default(),
-            Parameter::OtherHome(user) => dir::home(&self.word(user)?),
-            Parameter::MyHome => dir::my_home(),
+            Parameter::String(s) => match self.var(s) {
+                Some(value) => value,
+                None => self.unset_parameter(p)?,
+            },
+            Parameter::Element {
+                name,
+                subscript: Subscript::Index(key),
+            } => {
+                let key = self.word(key)?;
+                let key = self.subscript(name, &key);
+
+                match self.array(name).and_then(|array| array.get(&key)) {
+                    Some(value) => value.to_string(),
+                    None => self.unset_parameter(p)?,
+                }
+            }
+            Parameter::Element { name, subscript } => {
+                let separator = match (subscript, self.var("IFS")) {
+                    (Subscript::Star, Some(ifs)) => ifs.chars().take(1).collect(),
+                    _ => String::from(" "),
+                };
+
+                self.elements(name).join(&separator)
+            }
+            Parameter::Length(p) => match &**p {
+                Parameter::Element {
+                    name,
+                    subscript: Subscript::At | Subscript::Star,
+                } => self.elements(name).len().to_string(),
+                p => self.parameter(p)?.chars().count().to_string(),
+            },
+            Parameter::Keys(name) => self.keys(name).join(" "),
+            Parameter::Status => self.exit_code.to_string(),
+            Parameter::Flags => self.flags(),
+            Parameter::Number(n) => match self.args.get(*n) {
+                Some(value) => value.clone(),
+                None => self.unset_parameter(p)?,
+            },
+            Parameter::OtherHome(user) => {
+                let user = self.word(user)?;
+
+                match user.as_str() {
+                    "+" => self.pwd.clone(),
+                    "-" => self.old_pwd.clone(),
+                    _ => match self.stack_index(&user) {
+                        Some(i) => self.directory_stack().swap_remove(i),
+                        None => dir::home(&user),
+                    },
+                }
+            }
+            Parameter::MyHome => self.home(),
         })
     }
 
+    fn unset_parameter(&mut self, p: &Parameter) -> Result<String> {
+        if !self.options.get(ShellOption::Nounset) {
+            return Ok(String::new());
+        }
+
+        let name = p.to_string();
+        let name = name[1..].trim_start_matches('{').trim_end_matches('}');
+        self.io.eprintln(format!("crsh: {name}: unbound variable"));
+
+        if !self.interactive {
+            return Err(Error::Flow(Flow::Exit(ExitStatus::FAILURE)));
+        }
+
+        Err(Error::Aborted {
+            code: ExitStatus::FAILURE,
+        })
+    }
+
+    fn trace(&mut self, name: &str, args: &[&str]) {
+        let prompt = self.var("PS4").unwrap_or_else(|| String::from("+ "));
+
+        let line = [name]
+            .iter()
+            .chain(args)
+            .map(|word| quote(word))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.io.eprintln(format!("{prompt}{line}"));
+    }
+
     fn redirection(
         &mut self,
         ctx: Option<io::Context>,
-        _redirections: &[Redirection],
+        redirections: &[Redirection],
         node: &Node,
-    ) -> Result<ExitCode> {
-        // todo: implement redirection
-        self.execute(ctx, node)
+    ) -> Result<ExitStatus> {
+        let mut io = match ctx {
+            Some(ctx) => ctx,
+            None => self.io.try_clone()?,
+        };
+
+        for redirection in redirections {
+            let target = self.word(&redirection.target)?;
+
+            if let Err(e) = self.redirect(&mut io, redirection, &target) {
+                self.io.eprintln(format!("crsh: {target}: {e}"));
+                return Ok(ExitStatus::FAILURE);
+            }
+        }
+
+        self.execute(Some(io), node)
     }
 
-    fn command(
+    pub(crate) fn redirect(
         &mut self,
-        ctx: Option<io::Context>,
-        name: &Word,
-        args: &[Word],
-    ) -> Result<ExitCode> {
-        if self.should_exit {
-            return Ok(self.exit_code);
+        io: &mut io::Context,
+        redirection: &Redirection,
+        target: &str,
+    ) -> std::io::Result<()> {
+        let mut options = OpenOptions::new();
+
+        let fd = match redirection.operator {
+            RedirectionOperator::Input => {
+                options.read(true);
+                redirection.fd.unwrap_or(0)
+            }
+            RedirectionOperator::Output | RedirectionOperator::Clobber => {
+                if redirection.operator == RedirectionOperator::Output
+                    && self.options.get(ShellOption::Noclobber)
+                    && Path::new(target).is_file()
+                {
+                    return Err(std::io::Error::new(
+                        ErrorKind::AlreadyExists,
+                        "cannot overwrite existing file",
+                    ));
+                }
+
+                options.write(true).create(true).truncate(true);
+                redirection.fd.unwrap_or(1)
+            }
+            RedirectionOperator::Append => {
+                options.append(true).create(true);
+                redirection.fd.unwrap_or(1)
+            }
+            RedirectionOperator::ReadWrite => {
+                options.read(true).write(true).create(true);
+                redirection.fd.unwrap_or(0)
+            }
+            RedirectionOperator::DupInput | RedirectionOperator::DupOutput => {
+                let fd = redirection.fd.unwrap_or(
+                    if redirection.operator == RedirectionOperator::DupInput {
+                        0
+                    } else {
+                        1
+                    },
+                );
+
+                if target == "-" {
+                    io.close(fd);
+                } else if let Ok(source) = target.parse() {
+                    io.dup(source, fd)?;
+                } else if redirection.operator == RedirectionOperator::DupOutput
+                    && redirection.fd.is_none()
+                {
+                    let file = File::create(target)?;
+                    io.set(2, io::Output::File(file.try_clone()?));
+                    io.set(1, io::Output::File(file));
+                } else {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidInput,
+                        "ambiguous redirect",
+                    ));
+                }
+
+                return Ok(());
+            }
+        };
+
+        let file = options.open(target)?;
+
+        match redirection.operator {
+            RedirectionOperator::Input => io.set(fd, io::Input::File(file)),
+            _ => io.set(fd, io::Output::File(file)),
         }
 
-        let name = self.word(name)?;
+        Ok(())
+    }
+
+    fn expand_command(&mut self, name: &Word, args: &[Word]) -> Result<(String, Vec<String>)> {
+        let mut fields = Vec::new();
 
-        let args_owned = args
-            .iter()
-            .map(|arg| self.word(arg))
-            .collect::<Result<Vec<_>>>()?;
+        for word in [name].into_iter().chain(args) {
+            fields.extend(self.fields(word)?);
+        }
 
-        let args = args_owned
-            .iter()
-            .map(|arg| arg.as_str())
-            .collect::<Vec<_>>();
+        let mut fields = fields.into_iter();
+        let name = fields.next().unwrap_or_default();
 
-        let mut io = match ctx {
+        Ok((name, fields.collect()))
+    }
+
+    fn assignment(&mut self, assignments: &[Assignment]) -> Result<ExitStatus> {
+        self.exit_code = ExitStatus::SUCCESS;
+        let mut failed = false;
+
+        for Assignment {
+            name,
+            subscript,
+            append,
+            value,
+        } in assignments
+        {
+            match (subscript, value) {
+                (_, AssignmentValue::Array(elements)) => {
+                    let mut array = match self.array(name) {
+                        Some(current) if *append => current.clone(),
+                        Some(Value::Associative(_)) => Value::Associative(Default::default()),
+                        _ => Value::Indexed(Default::default()),
+                    };
+
+                    for (key, element) in elements {
+                        if let Some(key) = key {
+                            let key = match (self.word(key)?, &array) {
+                                (key, Value::Associative(_)) => key,
+                                (key, _) => match self.variables.evaluate(&key) {
+                                    Ok(index) => index.to_string(),
+                                    Err(_) => key,
+                                },
+                            };
+
+                            let value = self.word(element)?;
+
+                            if !array.insert(&key, value) {
+                                self.io
+                                    .eprintln(format!("crsh: {name}[{key}]: bad array subscript"));
+                                failed = true;
+                            }
+                        } else if let Value::Associative(_) = array {
+                            self.io.eprintln(format!(
+                                "crsh: {name}: {element}: must use subscript when assigning associative array"
+                            ));
+                            failed = true;
+                        } else {
+                            array.push(self.fields(element)?);
+                        }
+                    }
+
+                    failed |= !self.set_value(name, array);
+                }
+                (key, AssignmentValue::Scalar(word)) => {
+                    let key = match key {
+                        Some(key) => Some(self.word(key)?),
+                        None => None,
+                    };
+
+                    let mut value = self.word(word)?;
+
+                    if *append {
+                        value = self.appended(name, key.as_deref(), &value);
+                    }
+
+                    failed |= !match key {
+                        Some(key) => self.set_element(name, &key, &value),
+                        None => self.set_var(name, &value),
+                    };
+                }
+            }
+        }
+
+        if failed {
+            Ok(ExitStatus::FAILURE)
+        } else {
+            Ok(self.exit_code)
+        }
+    }
+
+    /// The value that `name+=value` assigns, adding for integer variables and concatenating otherwise.
+    pub(crate) fn appended(&self, name: &str, key: Option<&str>, value: &str) -> String {
+        let current = match key {
+            Some(key) => self
+                .array(name)
+                .and_then(|array| array.get(&self.subscript(name, key)))
+                .map(String::from),
+            None => self.var(name),
+        };
+
+        let integer = self
+            .variables
+            .get(name)
+            .is_some_and(|variable| variable.attributes.integer);
+
+        match current {
+            Some(current) if integer => format!("{current}+({value})"),
+            Some(current) => current + value,
+            None => value.to_string(),
+        }
+    }
+
+    fn launch(
+        &mut self,
+        ctx: Option<io::Context>,
+        name: &str,
+        args: &[String],
+        pgid: Option<Pid>,
+        foreground: bool,
+    ) -> Result<Launch> {
+        let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>();
+
+        let io = match ctx {
             Some(ctx) => ctx,
             None => self.io.try_clone()?,
         };
 
-        if let Some(builtin) = Builtin::get(&name) {
-            Ok(builtin.run(self, &mut io, &args))
-        } else if let Some(path) = dir::find_on_path(&name) {
-            let mut cmd = std::process::Command::new(path);
-
-            let mut child = cmd
-                .stdin(io.input.try_clone()?)
-                .stdout(io.output.try_clone()?)
-                .stderr(io.error.try_clone()?)
-                .args(args)
-                .spawn()?;
-
-            drop(io);
-            drop(cmd);
-
-            Ok(child
-                .wait()?
-                .code()
-                .unwrap_or(0)
-                .try_into()
-                .unwrap_or_default())
+        self.run_trap(Trap::Debug);
+        self.check_flow()?;
+
+        if self.options.get(ShellOption::Xtrace) {
+            self.trace(name, &args);
+        }
+
+        if let Some(body) = self.functions.get(name).cloned() {
+            return self.call(io, &args, &body).map(Launch::Builtin);
+        }
+
+        self.start(io, name, &args, pgid, foreground)
+    }
+
+    /// Runs a function body with `args` as the positional parameters, in a new variable scope.
+    fn call(&mut self, io: io::Context, args: &[&str], body: &str) -> Result<ExitStatus> {
+        let mut call_args = self.args.iter().take(1).cloned().collect::<Vec<_>>();
+        call_args.extend(args.iter().map(|arg| arg.to_string()));
+        let args = std::mem::replace(&mut self.args, call_args);
+
+        self.variables.push_scope();
+        self.function_depth += 1;
+
+        let result = match Parser::new(body).parse() {
+            Ok(ast) => self.execute(Some(io), &ast),
+            Err(errors) => {
+                for e in errors {
+                    self.io.eprintln(e.to_string());
+                }
+
+                Ok(ExitStatus::MISUSE)
+            }
+        };
+
+        self.function_depth -= 1;
+        self.variables.pop_scope();
+        self.args = args;
+
+        match result {
+            Err(Error::Flow(Flow::Return(code))) => Ok(code),
+            result => result,
+        }
+    }
+
+    /// Whether `name` runs inside the shell, as a function or builtin, rather than as a process.
+    fn runs_in_shell(&self, name: &str) -> bool {
+        self.functions.contains_key(name) || Builtin::get(name).is_some()
+    }
+
+    fn start(
+        &mut self,
+        mut io: io::Context,
+        name: &str,
+        args: &[&str],
+        pgid: Option<Pid>,
+        foreground: bool,
+    ) -> Result<Launch> {
+        if let Some(builtin) = Builtin::get(name) {
+            let code = builtin.run(self, &mut io, args);
+            self.check_flow()?;
+            return Ok(Launch::Builtin(code));
+        }
+
+        let path = if name.contains('/') {
+            PathBuf::from(name)
+        } else if let Some(path) = self.find_command(name) {
+            path
         } else {
             self.io.eprintln(format!("crsh: command not found: {name}"));
-            Ok(ExitCode::Unavailable)
+            return Ok(Launch::Builtin(ExitStatus::NOT_FOUND));
+        };
+
+        let env = self.variables.environment();
+
+        let spawn = |program: &Path, args: &[&str]| -> std::io::Result<Pid> {
+            let mut cmd = std::process::Command::new(program);
+            cmd.env_clear().envs(&env);
+            process::prepare_child(&mut cmd, pgid, foreground);
+
+            io.attach(&mut cmd)?;
+            let child = cmd.args(args).spawn()?;
+
+            Ok(child.id() as Pid)
+        };
+
+        let result = match spawn(&path, args) {
+            Err(e) if process::is_exec_format_error(&e) => {
+                std::env::current_exe().and_then(|crsh| spawn(&crsh, &[&[name], args].concat()))
+            }
+            result => result,
+        };
+
+        match result {
+            Ok(pid) => Ok(Launch::Process(pid)),
+            Err(e) => {
+                let (message, code) = match e.kind() {
+                    _ if path.is_dir() => ("is a directory", ExitStatus::NOT_EXECUTABLE),
+                    ErrorKind::NotFound => ("no such file or directory", ExitStatus::NOT_FOUND),
+                    ErrorKind::PermissionDenied => {
+                        ("permission denied", ExitStatus::NOT_EXECUTABLE)
+                    }
+                    _ => {
+                        self.io.eprintln(format!("crsh: {name}: {e}"));
+                        return Ok(Launch::Builtin(ExitStatus::NOT_EXECUTABLE));
+                    }
+                };
+
+                self.io.eprintln(format!("crsh: {message}: {name}"));
+                Ok(Launch::Builtin(code))
+            }
+        }
+    }
+
+    /// Runs a builtin or external command without the DEBUG trap or tracing, as `command` does.
+    pub(crate) fn run_command(
+        &mut self,
+        io: io::Context,
+        name: &str,
+        args: &[&str],
+    ) -> Result<ExitStatus> {
+        match self.start(io, name, args, None, true)? {
+            Launch::Builtin(code) => Ok(code),
+            Launch::Process(pid) => {
+                let mut job = Job::new([&[name], args].concat().join(" "));
+                job.push(pid);
+                self.foreground(job)
+            }
         }
     }
 
-    fn list(&mut self, ctx: Option<io::Context>, nodes: &[Node]) -> Result<ExitCode> {
-        let mut exit_code = ExitCode::Ok;
+    fn command(&mut self, ctx: Option<io::Context>, command: &Command) -> Result<ExitStatus> {
+        let (name, args) = self.expand_command(&command.name, &command.args)?;
+
+        let code = match self.launch(ctx, &name, &args, None, true)? {
+            Launch::Builtin(code) => code,
+            Launch::Process(pid) => {
+                let mut job = Job::new(command.to_string());
+                job.push(pid);
+                self.foreground(job)?
+            }
+        };
+
+        self.set_array("PIPESTATUS", &[code.to_string()]);
+        Ok(code)
+    }
+
+    fn fork(
+        &mut self,
+        pgid: Option<Pid>,
+        foreground: bool,
+        f: impl FnOnce(&mut Self) -> Result<ExitStatus>,
+    ) -> Result<Pid> {
+        self.io.flush();
+
+        match process::fork(pgid, foreground)? {
+            Fork::Parent(pid) => Ok(pid),
+            Fork::Child => {
+                self.jobs = JobTable::default();
+                self.traps.reset();
+
+                let code = match f(self) {
+                    Ok(code) => code,
+                    Err(e) => self.report(e),
+                };
+
+                self.exit_code = code;
+                self.run_exit_trap();
+                let code = self.exit_code;
+
+                self.io.flush();
+                std::process::exit(code.into())
+            }
+        }
+    }
+
+    /// Runs a builtin in a child process, so that it does not affect the state of the current shell.
+    fn fork_builtin(
+        &mut self,
+        ctx: Option<io::Context>,
+        name: &str,
+        args: &[String],
+        foreground: bool,
+    ) -> Result<Pid> {
+        self.fork(None, foreground, |shell| {
+            shell.forked_builtin(ctx, name, args, foreground)
+        })
+    }
+
+    /// Runs a builtin within a child that has already been forked for it.
+    fn forked_builtin(
+        &mut self,
+        ctx: Option<io::Context>,
+        name: &str,
+        args: &[String],
+        foreground: bool,
+    ) -> Result<ExitStatus> {
+        match self.launch(ctx, name, args, None, foreground)? {
+            Launch::Builtin(code) => Ok(code),
+            Launch::Process(_) => Ok(ExitStatus::SUCCESS),
+        }
+    }
+
+    /// Forks a pipeline stage. The child closes `unused`, the read end of its own output pipe, so
+    /// that it sees EPIPE or SIGPIPE once the next stage exits rather than blocking forever.
+    fn fork_stage(
+        &mut self,
+        pgid: Option<Pid>,
+        foreground: bool,
+        unused: Option<RawFd>,
+        f: impl FnOnce(&mut Self) -> Result<ExitStatus>,
+    ) -> Result<Pid> {
+        self.fork(pgid, foreground, |shell| {
+            if let Some(fd) = unused {
+                io::close(fd);
+            }
+
+            f(shell)
+        })
+    }
+
+    fn subshell(&mut self, ctx: Option<io::Context>, node: &Node) -> Result<ExitStatus> {
+        let pid = self.fork(None, true, |shell| shell.execute(ctx, node))?;
+        let mut job = Job::new(format!("({node})"));
+        job.push(pid);
+        self.foreground(job)
+    }
+
+    fn background(&mut self, ctx: Option<io::Context>, node: &Node) -> Result<ExitStatus> {
+        if let Node::Pipeline { nodes } = node {
+            let (job, _) = self.spawn_pipeline(ctx, nodes, false)?;
+            let pid = job.processes.last().map(|p| p.pid).unwrap_or_default();
+            let id = self.jobs.insert(job);
+
+            if process::job_control() {
+                self.io.eprintln(format!("[{id}] {pid}"));
+            }
+
+            return Ok(ExitStatus::SUCCESS);
+        }
+
+        let pid = match node {
+            Node::Command {
+                command: Command { name, args },
+            } => {
+                let (name, args) = self.expand_command(name, args)?;
+
+                if self.runs_in_shell(&name) {
+                    self.fork_builtin(ctx, &name, &args, false)?
+                } else {
+                    match self.launch(ctx, &name, &args, None, false)? {
+                        Launch::Builtin(code) => return Ok(code),
+                        Launch::Process(pid) => pid,
+                    }
+                }
+            }
+            Node::Subshell { node } => self.fork(None, false, |shell| shell.execute(ctx, node))?,
+            node => self.fork(None, false, |shell| shell.execute(ctx, node))?,
+        };
+
+        let mut job = Job::new(node.to_string());
+        job.push(pid);
+        let id = self.jobs.insert(job);
+
+        if process::job_control() {
+            self.io.eprintln(format!("[{id}] {pid}"));
+        }
+
+        Ok(ExitStatus::SUCCESS)
+    }
+
+    pub(crate) fn foreground(&mut self, job: Job) -> Result<ExitStatus> {
+        Ok(self
+            .wait_foreground(job)?
+            .last()
+            .copied()
+            .unwrap_or_default())
+    }
+
+    fn wait_foreground(&mut self, mut job: Job) -> Result<Vec<ExitStatus>> {
+        if let Some(pgid) = job.pgid {
+            process::give_terminal(pgid, job.modes.as_ref())?;
+        }
+
+        let result = job.wait();
+
+        if job.pgid.is_some() {
+            job.modes = process::reclaim_terminal()?;
+        }
+
+        result?;
+        let codes = job.exit_codes();
+
+        if job.state() == JobState::Stopped {
+            job.notified = true;
+            let id = self.jobs.insert(job);
+
+            if let Some(line) = self.jobs.display(id) {
+                self.io.eprintln(format!("\n{line}"));
+            }
+        }
+
+        Ok(codes)
+    }
+
+    fn list(&mut self, ctx: Option<io::Context>, nodes: &[Node]) -> Result<ExitStatus> {
+        let mut exit_code = ExitStatus::SUCCESS;
 
         if let Some(ref ctx) = ctx {
             for node in nodes {
@@ -148,60 +792,247 @@ impl Shell {
         Ok(exit_code)
     }
 
-    fn pipeline(&mut self, ctx: Option<io::Context>, nodes: &[Node]) -> Result<ExitCode> {
+    fn pipeline(&mut self, ctx: Option<io::Context>, nodes: &[Node]) -> Result<ExitStatus> {
         match nodes.len() {
-            0 => Ok(ExitCode::Ok),
+            0 => Ok(ExitStatus::SUCCESS),
             1 => self.execute(ctx, &nodes[0]),
-            len => {
-                let mut pipes = Vec::new();
+            _ => {
+                let (job, stages) = self.spawn_pipeline(ctx, nodes, true)?;
+                let mut codes = self.wait_foreground(job)?.into_iter();
 
-                let io = match ctx {
-                    Some(ctx) => ctx,
-                    None => self.io.try_clone()?,
-                };
+                let pipe_status = stages
+                    .into_iter()
+                    .map(|stage| match stage {
+                        Stage::Done(code) => code,
+                        Stage::Process => codes.next().unwrap_or_default(),
+                    })
+                    .collect::<Vec<_>>();
 
-                let first_ctx = {
-                    let (reader, writer) = io::pipe()?;
-                    pipes.push((reader.try_clone()?, writer.try_clone()?));
+                let values = pipe_status
+                    .iter()
+                    .map(|code| code.to_string())
+                    .collect::<Vec<_>>();
+                self.set_array("PIPESTATUS", &values);
+                let last = pipe_status.last().copied().unwrap_or_default();
 
-                    io::Context {
-                        input: io.input.try_clone()?,
-                        output: writer.into(),
-                        error: io.error.try_clone()?,
-                    }
-                };
+                if self.options.get(ShellOption::Pipefail) {
+                    Ok(pipe_status
+                        .iter()
+                        .rev()
+                        .find(|code| !code.is_success())
+                        .copied()
+                        .unwrap_or(last))
+                } else {
+                    Ok(last)
+                }
+            }
+        }
+    }
+
+    /// Starts every stage of a pipeline as a process of one job. In the foreground the last stage
+    /// may instead run in the current shell, which is recorded as a finished stage.
+    fn spawn_pipeline(
+        &mut self,
+        ctx: Option<io::Context>,
+        nodes: &[Node],
+        foreground: bool,
+    ) -> Result<(Job, Vec<Stage>)> {
+        let io = match ctx {
+            Some(ctx) => ctx,
+            None => self.io.try_clone()?,
+        };
+
+        let description = nodes
+            .iter()
+            .map(|node| node.to_string())
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        let len = nodes.len();
+        let mut job = Job::new(description);
+        let mut stages = Vec::new();
+        let mut input = None;
+
+        for (i, node) in nodes.iter().enumerate() {
+            let mut stage_ctx = io.try_clone()?;
+            let mut output = None;
+
+            if let Some(input) = input.take() {
+                stage_ctx.set(0, input);
+            }
 
-                _ = self.execute(Some(first_ctx), &nodes[0])?;
+            if i < len - 1 {
+                let (reader, writer) = io::pipe()?;
+                stage_ctx.set(1, writer);
+                output = Some(reader);
+            }
+
+            let unused = output.as_ref().map(|reader| reader.as_raw_fd());
+
+            // Only the last stage may run in the current shell, as earlier stages have to run
+            // concurrently with the stages reading their output
+            let inline = foreground && i == len - 1 && self.options.get(ShellOption::Lastpipe);
 
-                for node in &nodes[1..len - 1] {
-                    let new_ctx = {
-                        let (last_reader, _) = pipes.pop().unwrap();
-                        let (reader, writer) = io::pipe()?;
-                        pipes.push((reader.try_clone()?, writer.try_clone()?));
+            match node {
+                Node::Command {
+                    command: Command { name, args },
+                } => {
+                    let (name, args) = self.expand_command(name, args)?;
 
-                        io::Context {
-                            input: last_reader.into(),
-                            output: writer.into(),
-                            error: io.error.try_clone()?,
+                    if !inline && self.runs_in_shell(&name) {
+                        let pid = self.fork_stage(job.pgid, foreground, unused, |shell| {
+                            shell.forked_builtin(Some(stage_ctx), &name, &args, foreground)
+                        })?;
+
+                        job.push(pid);
+                        stages.push(Stage::Process);
+                    } else {
+                        match self.launch(Some(stage_ctx), &name, &args, job.pgid, foreground)? {
+                            Launch::Builtin(code) => stages.push(Stage::Done(code)),
+                            Launch::Process(pid) => {
+                                job.push(pid);
+                                stages.push(Stage::Process);
+                            }
                         }
-                    };
+                    }
+                }
+                Node::Subshell { node } => {
+                    let pid = self.fork_stage(job.pgid, foreground, unused, |shell| {
+                        shell.execute(Some(stage_ctx), node)
+                    })?;
 
-                    _ = self.execute(Some(new_ctx), node)?;
+                    job.push(pid);
+                    stages.push(Stage::Process);
+                }
+                node if inline => {
+                    stages.push(Stage::Done(self.execute(Some(stage_ctx), node)?));
                 }
+                node => {
+                    let pid = self.fork_stage(job.pgid, foreground, unused, |shell| {
+                        shell.execute(Some(stage_ctx), node)
+                    })?;
 
-                let last_ctx = {
-                    let (reader, _) = pipes.pop().unwrap();
+                    job.push(pid);
+                    stages.push(Stage::Process);
+                }
+            }
 
-                    io::Context {
-                        input: reader.into(),
-                        output: io.output.try_clone()?,
-                        error: io.error.try_clone()?,
-                    }
-                };
+            input = output;
+        }
+
+        Ok((job, stages))
+    }
+
+    fn while_loop(
+        &mut self,
+        ctx: Option<io::Context>,
+        until: bool,
+        predicate: &Node,
+        body: &Node,
+    ) -> Result<ExitStatus> {
+        self.loop_depth += 1;
+        let result = self.repeat(ctx, until, predicate, body);
+        self.loop_depth -= 1;
+        result
+    }
 
-                self.execute(Some(last_ctx), &nodes[len - 1])
+    fn repeat(
+        &mut self,
+        ctx: Option<io::Context>,
+        until: bool,
+        predicate: &Node,
+        body: &Node,
+    ) -> Result<ExitStatus> {
+        let mut code = ExitStatus::SUCCESS;
+
+        loop {
+            self.errexit_exempt += 1;
+            let result = Self::clone_context(&ctx).and_then(|ctx| self.execute(ctx, predicate));
+            self.errexit_exempt -= 1;
+
+            match Self::step(result)? {
+                Step::Next(test) if test.is_success() == until => return Ok(code),
+                Step::Next(_) | Step::Continue => {}
+                Step::Break => return Ok(ExitStatus::SUCCESS),
+            }
+
+            let result = Self::clone_context(&ctx).and_then(|ctx| self.execute(ctx, body));
+
+            match Self::step(result)? {
+                Step::Next(last) => code = last,
+                Step::Continue => code = ExitStatus::SUCCESS,
+                Step::Break => return Ok(ExitStatus::SUCCESS),
+            }
+        }
+    }
+
+    fn for_loop(
+        &mut self,
+        ctx: Option<io::Context>,
+        name: &str,
+        words: Option<&[Word]>,
+        body: &Node,
+    ) -> Result<ExitStatus> {
+        let values = match words {
+            Some(words) => {
+                let mut values = Vec::new();
+
+                for word in words {
+                    values.extend(self.fields(word)?);
+                }
+
+                values
+            }
+            None => self.args.iter().skip(1).cloned().collect(),
+        };
+
+        self.loop_depth += 1;
+        let result = self.each(ctx, name, values, body);
+        self.loop_depth -= 1;
+        result
+    }
+
+    fn each(
+        &mut self,
+        ctx: Option<io::Context>,
+        name: &str,
+        values: Vec<String>,
+        body: &Node,
+    ) -> Result<ExitStatus> {
+        let mut code = ExitStatus::SUCCESS;
+
+        for value in values {
+            if !self.set_var(name, &value) {
+                return Ok(ExitStatus::FAILURE);
+            }
+
+            let result = Self::clone_context(&ctx).and_then(|ctx| self.execute(ctx, body));
+
+            match Self::step(result)? {
+                Step::Next(last) => code = last,
+                Step::Continue => code = ExitStatus::SUCCESS,
+                Step::Break => return Ok(ExitStatus::SUCCESS),
             }
         }
+
+        Ok(code)
+    }
+
+    /// Takes a `break` or `continue` aimed at the innermost loop, passing those aimed further out
+    /// on with one loop fewer to unwind.
+    fn step(result: Result<ExitStatus>) -> Result<Step> {
+        match result {
+            Ok(code) => Ok(Step::Next(code)),
+            Err(Error::Flow(Flow::Break(1))) => Ok(Step::Break),
+            Err(Error::Flow(Flow::Continue(1))) => Ok(Step::Continue),
+            Err(Error::Flow(Flow::Break(n))) => Err(Error::Flow(Flow::Break(n - 1))),
+            Err(Error::Flow(Flow::Continue(n))) => Err(Error::Flow(Flow::Continue(n - 1))),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn clone_context(ctx: &Option<io::Context>) -> Result<Option<io::Context>> {
+        Ok(ctx.as_ref().map(io::Context::try_clone).transpose()?)
     }
 
     fn logical(
@@ -210,17 +1041,37 @@ impl Shell {
         and: bool,
         left: &Node,
         right: &Node,
-    ) -> Result<ExitCode> {
+    ) -> Result<ExitStatus> {
+        self.errexit_exempt += 1;
+
         let left_result = if let Some(ref ctx) = ctx {
-            self.execute(Some(ctx.try_clone()?), left)?
+            ctx.try_clone()
+                .map_err(Error::from)
+                .and_then(|ctx| self.execute(Some(ctx), left))
         } else {
-            self.execute(None, left)?
+            self.execute(None, left)
         };
 
-        if (left_result == ExitCode::Ok) == and {
+        self.errexit_exempt -= 1;
+        let left_result = left_result?;
+
+        if (left_result == ExitStatus::SUCCESS) == and {
             self.execute(ctx, right)
         } else {
             Ok(left_result)
         }
     }
 }
+
+/// Single quotes `word` unless it consists only of characters that are safe unquoted.
+pub(crate) fn quote(word: &str) -> String {
+    if !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || "%+,-./:=@_^".contains(c))
+    {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
@@ -0,0 +1,19 @@
+use crate::ExitStatus;
+
+/// Non-local control flow raised by a builtin, unwinding the executor until something handles it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Return(ExitStatus),
+    Exit(ExitStatus),
+    Break(usize),
+    Continue(usize),
+}
+
+impl Flow {
+    pub(crate) fn code(self) -> ExitStatus {
+        match self {
+            Self::Return(code) | Self::Exit(code) => code,
+            Self::Break(_) | Self::Continue(_) => ExitStatus::SUCCESS,
+        }
+    }
+}
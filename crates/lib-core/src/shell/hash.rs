@@ -0,0 +1,78 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use lib_os::dir;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Hashed {
+    pub(crate) path: PathBuf,
+    pub(crate) hits: usize,
+}
+
+/// Remembers where commands were found on `PATH`, forgetting everything whenever `PATH` changes.
+#[derive(Debug, Default)]
+pub(crate) struct CommandCache {
+    path: Option<String>,
+    entries: BTreeMap<String, Hashed>,
+}
+
+impl CommandCache {
+    pub(crate) fn validate(&mut self, path: Option<String>) {
+        if self.path != path {
+            self.path = path;
+            self.entries.clear();
+        }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Hashed> {
+        self.entries.get(name)
+    }
+
+    pub(crate) fn find(&mut self, name: &str) -> Option<PathBuf> {
+        if let Some(hashed) = self.entries.get_mut(name)
+            && dir::is_executable(&hashed.path)
+        {
+            hashed.hits += 1;
+            return Some(hashed.path.clone());
+        }
+
+        let path = self.search(name);
+
+        match &path {
+            Some(path) => self.insert(name, path.clone(), 1),
+            None => _ = self.entries.remove(name),
+        }
+
+        path
+    }
+
+    pub(crate) fn search(&self, name: &str) -> Option<PathBuf> {
+        dir::search_path(self.path.as_deref()?.as_ref(), name).next()
+    }
+
+    pub(crate) fn search_all(&self, name: &str) -> Vec<PathBuf> {
+        match &self.path {
+            Some(path) => dir::search_path(path.as_ref(), name).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, name: &str, path: PathBuf, hits: usize) {
+        self.entries.insert(name.to_string(), Hashed { path, hits });
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&String, &Hashed)> {
+        self.entries.iter()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -3,22 +3,45 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use sysexits::ExitCode;
+use lib_os::{dir, io, process, signal};
 
-use lib_os::{dir, io};
-
-use crate::{config::Config, parsing::Parser};
+use crate::{
+    ExitStatus, Result,
+    config::Config,
+    error::Error,
+    parsing::Parser,
+    shell::{
+        CommandCache, Flow, JobState, JobTable, Options, ShellOption, Trap, Traps, Value, Variable,
+        Variables,
+    },
+};
 
 #[derive(Debug)]
 pub struct Shell {
     pub(crate) io: io::Context,
     pub(crate) config: Config,
-    pub(crate) exit_code: ExitCode,
-    pub(crate) should_exit: bool,
+    pub(crate) exit_code: ExitStatus,
     pub(crate) pwd: String,
     pub(crate) old_pwd: String,
+    pub(crate) dirs: Vec<String>,
     pub(crate) args: Vec<String>,
-    pub(crate) _variables: HashMap<String, String>, // todo
+    pub(crate) jobs: JobTable,
+    pub(crate) options: Options,
+    pub(crate) interactive: bool,
+    pub(crate) errexit_exempt: usize,
+    pub(crate) traps: Traps,
+    pub(crate) flow: Option<Flow>,
+    pub(crate) source_depth: usize,
+    /// Number of loops currently running, so that `break` and `continue` know how far they can
+    /// reach.
+    pub(crate) loop_depth: usize,
+    /// Number of function calls currently running, so that `return` knows it is in one.
+    pub(crate) function_depth: usize,
+    /// Bodies of the defined functions by name, parsed again on each call.
+    pub(crate) functions: HashMap<String, String>,
+    pub(crate) getopts_position: (usize, usize),
+    pub(crate) commands: CommandCache,
+    pub(crate) variables: Variables,
 }
 
 impl Default for Shell {
@@ -26,74 +49,330 @@ impl Default for Shell {
         Self {
             io: io::Context::default(),
             config: Config::default(),
-            exit_code: ExitCode::Ok,
-            should_exit: false,
-            pwd: dir::current(),
+            exit_code: ExitStatus::SUCCESS,
+            pwd: dir::current_logical(),
             old_pwd: String::new(),
+            dirs: Vec::new(),
             args: std::env::args().collect(),
-            _variables: HashMap::new(),
+            jobs: JobTable::default(),
+            options: Options::default(),
+            interactive: false,
+            errexit_exempt: 0,
+            traps: Traps::default(),
+            flow: None,
+            source_depth: 0,
+            loop_depth: 0,
+            function_depth: 0,
+            functions: HashMap::new(),
+            getopts_position: (1, 0),
+            commands: CommandCache::default(),
+            variables: Variables::default(),
         }
     }
 }
 
 impl Shell {
-    pub fn interpret(&mut self, input: &str) -> ExitCode {
-        match Parser::new(input).parse() {
-            Ok(ast) => {
-                println!("\n{ast:#?}\n");
-
-                match self.execute(None, &ast) {
-                    Ok(code) => {
-                        self.set_exit_code(code);
-                    }
-                    Err(e) => {
-                        self.io.eprintln(format!("crsh: interpreter error: {e:#?}"));
-                        self.set_exit_code(ExitCode::DataErr);
-                    }
-                }
+    pub fn interpret(&mut self, input: &str) -> ExitStatus {
+        let code = match self.evaluate(input) {
+            Ok(code) => code,
+            Err(Error::Flow(Flow::Exit(code))) => {
+                self.flow = Some(Flow::Exit(code));
+                code
             }
+            Err(e) => self.report(e),
+        };
+
+        self.set_exit_code(code);
+        code
+    }
+
+    pub(crate) fn evaluate(&mut self, input: &str) -> Result<ExitStatus> {
+        match Parser::new(input).parse() {
+            Ok(ast) => self.execute(None, &ast),
             Err(errors) => {
                 for e in errors {
                     self.io.eprintln(e.to_string());
                 }
 
-                self.set_exit_code(ExitCode::DataErr);
+                self.set_exit_code(ExitStatus::MISUSE);
+                Ok(ExitStatus::MISUSE)
+            }
+        }
+    }
+
+    pub(crate) fn var(&self, name: &str) -> Option<String> {
+        self.variables
+            .get(name)?
+            .value
+            .as_ref()?
+            .scalar()
+            .map(String::from)
+    }
+
+    pub(crate) fn set_var(&mut self, name: &str, value: &str) -> bool {
+        self.set_value(name, Value::Scalar(value.to_string()))
+    }
+
+    /// Assigns a value, reporting an error and returning false if the variable is readonly or
+    /// the value is not valid for its attributes.
+    pub(crate) fn set_value(&mut self, name: &str, value: Value) -> bool {
+        let result = self.variables.set(name, value);
+        self.assigned(name, result)
+    }
+
+    /// Assigns one element of an array, creating an indexed array if `name` is unset.
+    pub(crate) fn set_element(&mut self, name: &str, key: &str, value: &str) -> bool {
+        let key = self.subscript(name, key);
+        let result = self.variables.set_element(name, &key, value.to_string());
+        self.assigned(&format!("{name}[{key}]"), result)
+    }
+
+    fn assigned(&mut self, target: &str, result: std::result::Result<(), String>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                self.io.eprintln(format!("crsh: {target}: {e}"));
+                false
             }
+        }
+    }
+
+    /// Evaluates an indexed array subscript arithmetically, leaving associative keys as they are.
+    pub(crate) fn subscript(&self, name: &str, key: &str) -> String {
+        match self.variables.get(name) {
+            Some(Variable {
+                value: Some(Value::Associative(_)),
+                ..
+            }) => key.to_string(),
+            _ => match self.variables.evaluate(key) {
+                Ok(index) => index.to_string(),
+                Err(_) => key.to_string(),
+            },
+        }
+    }
+
+    pub(crate) fn unset_element(
+        &mut self,
+        name: &str,
+        key: &str,
+    ) -> std::result::Result<(), String> {
+        let key = self.subscript(name, key);
+        self.variables.unset_element(name, &key)
+    }
+
+    /// The home directory for tilde expansion and `cd`, which follows `HOME` when it is set.
+    pub(crate) fn home(&self) -> String {
+        self.var("HOME").unwrap_or_else(dir::my_home)
+    }
+
+    pub(crate) fn find_command(&mut self, name: &str) -> Option<PathBuf> {
+        self.commands.validate(self.var("PATH"));
+        self.commands.find(name)
+    }
+
+    pub(crate) fn change_dir(&mut self, path: &str, physical: bool) -> std::io::Result<()> {
+        let logical = dir::normalize(Path::new(&self.pwd).join(path));
+
+        let pwd = if !physical && std::env::set_current_dir(&logical).is_ok() {
+            logical.to_string_lossy().to_string()
+        } else {
+            std::env::set_current_dir(path)?;
+            dir::current()
         };
 
-        self.exit_code
+        self.old_pwd = std::mem::replace(&mut self.pwd, pwd);
+        self.set_var("OLDPWD", &self.old_pwd.clone());
+        self.set_var("PWD", &self.pwd.clone());
+        Ok(())
+    }
+
+    pub(crate) fn directory_stack(&self) -> Vec<String> {
+        std::iter::once(self.pwd.clone())
+            .chain(self.dirs.iter().cloned())
+            .collect()
+    }
+
+    /// Resolves `N`, `+N` or `-N` to an index into the directory stack, where `-N` counts from the
+    /// bottom.
+    pub(crate) fn stack_index(&self, spec: &str) -> Option<usize> {
+        let (from_bottom, digits) = match spec.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, spec.strip_prefix('+').unwrap_or(spec)),
+        };
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let len = self.dirs.len() + 1;
+        let n = digits.parse::<usize>().ok().filter(|n| *n < len)?;
+
+        Some(if from_bottom { len - 1 - n } else { n })
+    }
+
+    pub(crate) fn set_array(&mut self, name: &str, values: &[String]) {
+        self.set_value(name, Value::indexed(values.iter().cloned()));
+    }
+
+    pub(crate) fn run_nested(&mut self, input: &str) -> ExitStatus {
+        match self.evaluate(input) {
+            Ok(code) => code,
+            Err(Error::Flow(flow)) => {
+                self.flow = Some(flow);
+                flow.code()
+            }
+            Err(e) => self.report(e),
+        }
     }
 
-    pub fn stdin(&mut self) -> &mut io::Input {
-        &mut self.io.input
+    pub(crate) fn report(&mut self, error: Error) -> ExitStatus {
+        match error {
+            Error::Aborted { code } => code,
+            Error::Flow(flow) => flow.code(),
+            e => {
+                self.io.eprintln(format!("crsh: interpreter error: {e:#?}"));
+                ExitStatus::FAILURE
+            }
+        }
+    }
+
+    pub fn stdin(&mut self) -> &mut io::Handle {
+        self.io.input()
     }
 
-    pub fn stdout(&mut self) -> &mut io::Output {
-        &mut self.io.output
+    pub fn stdout(&mut self) -> &mut io::Handle {
+        self.io.output()
     }
 
-    pub fn stderr(&mut self) -> &mut io::Output {
-        &mut self.io.error
+    pub fn stderr(&mut self) -> &mut io::Handle {
+        self.io.error()
     }
 
     pub fn should_exit(&self) -> bool {
-        self.should_exit
+        matches!(self.flow, Some(Flow::Exit(_)))
     }
 
-    pub fn exit_code(&self) -> ExitCode {
+    pub fn exit_code(&self) -> ExitStatus {
         self.exit_code
     }
 
-    pub fn set_exit_code(&mut self, code: ExitCode) {
+    pub fn set_exit_code(&mut self, code: ExitStatus) {
         self.exit_code = code;
     }
 
+    pub fn option(&self, option: ShellOption) -> bool {
+        self.options.get(option)
+    }
+
+    pub fn set_option(&mut self, option: ShellOption, value: bool) {
+        self.options.set(option, value);
+    }
+
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.interactive = interactive;
+    }
+
+    pub(crate) fn flags(&self) -> String {
+        let mut flags = self.options.flags();
+
+        if process::job_control() {
+            flags.push('m');
+        }
+
+        if self.interactive {
+            flags.push('i');
+        }
+
+        flags
+    }
+
+    pub fn run_pending_traps(&mut self) {
+        for sig in signal::pending() {
+            self.run_trap(Trap::Signal(sig));
+        }
+    }
+
+    pub fn run_exit_trap(&mut self) {
+        if self.traps.get(Trap::Exit).is_some() {
+            self.run_trap(Trap::Exit);
+            _ = self.traps.set(Trap::Exit, None);
+        }
+    }
+
+    pub(crate) fn run_trap(&mut self, trap: Trap) {
+        if self.traps.running {
+            return;
+        }
+
+        let Some(action) = self.traps.get(trap).filter(|a| !a.is_empty()) else {
+            return;
+        };
+
+        let action = action.to_string();
+        let code = self.exit_code;
+        let pending = self.flow.take();
+
+        self.traps.running = true;
+        self.interpret(&action);
+        self.traps.running = false;
+
+        if !self.should_exit() {
+            self.flow = pending;
+            self.exit_code = code;
+        }
+    }
+
+    pub fn enable_job_control(&mut self) {
+        if let Err(e) = process::enable_job_control() {
+            self.io
+                .eprintln(format!("crsh: cannot enable job control: {e}"));
+        }
+    }
+
+    pub fn notify_jobs(&mut self) {
+        for id in self.jobs.ids() {
+            let Some(job) = self.jobs.get_mut(id) else {
+                continue;
+            };
+
+            _ = job.poll();
+            let state = job.state();
+
+            if state != JobState::Running && !job.notified {
+                job.notified = true;
+
+                if let Some(line) = self.jobs.display(id) {
+                    self.io.eprintln(line);
+                }
+            }
+
+            if state == JobState::Done {
+                self.jobs.remove(id);
+            }
+        }
+    }
+
+    pub fn hang_up_jobs(&mut self) {
+        for id in self.jobs.ids() {
+            if let Some(job) = self.jobs.remove(id)
+                && !job.nohup
+                && let Some(pgid) = job.pgid
+            {
+                _ = process::hang_up(pgid);
+            }
+        }
+    }
+
+    pub fn set_args(&mut self, args: Vec<String>) {
+        self.args = args;
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
 
     pub fn pretty_pwd(&self) -> Option<String> {
-        let home = dir::my_home();
+        let home = self.home();
 
         if self.pwd.starts_with(&home) {
             Some(self.pwd.replacen(&home, "~", 1))
@@ -0,0 +1,265 @@
+use lib_os::process::{self, Pid, TerminalModes, WaitStatus};
+
+use crate::ExitStatus;
+
+#[derive(Debug)]
+pub(crate) struct Process {
+    pub(crate) pid: Pid,
+    pub(crate) status: Option<WaitStatus>,
+}
+
+impl Process {
+    fn exit_code(&self) -> ExitStatus {
+        self.status.map(ExitStatus::from).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JobState {
+    Running,
+    Stopped,
+    Done,
+}
+
+#[derive(Debug)]
+pub(crate) struct Job {
+    pub(crate) id: usize,
+    pub(crate) pgid: Option<Pid>,
+    pub(crate) processes: Vec<Process>,
+    pub(crate) command: String,
+    pub(crate) modes: Option<TerminalModes>,
+    pub(crate) notified: bool,
+    pub(crate) nohup: bool,
+}
+
+impl Job {
+    pub(crate) fn new(command: String) -> Self {
+        Self {
+            id: 0,
+            pgid: None,
+            processes: Vec::new(),
+            command,
+            modes: None,
+            notified: false,
+            nohup: false,
+        }
+    }
+
+    pub(crate) fn push(&mut self, pid: Pid) {
+        if self.pgid.is_none() && process::job_control() {
+            self.pgid = Some(pid);
+        }
+
+        self.processes.push(Process { pid, status: None });
+    }
+
+    pub(crate) fn state(&self) -> JobState {
+        if self
+            .processes
+            .iter()
+            .all(|p| p.status.is_some_and(|s| s.is_finished()))
+        {
+            JobState::Done
+        } else if self
+            .processes
+            .iter()
+            .any(|p| matches!(p.status, Some(WaitStatus::Stopped(_))))
+        {
+            JobState::Stopped
+        } else {
+            JobState::Running
+        }
+    }
+
+    pub(crate) fn exit_codes(&self) -> Vec<ExitStatus> {
+        self.processes.iter().map(|p| p.exit_code()).collect()
+    }
+
+    pub(crate) fn state_description(&self) -> String {
+        match self.state() {
+            JobState::Running => String::from("Running"),
+            JobState::Stopped => String::from("Stopped"),
+            JobState::Done => match self.processes.last().and_then(|p| p.status) {
+                Some(WaitStatus::Exited(0)) => String::from("Done"),
+                Some(WaitStatus::Exited(code)) => format!("Exit {code}"),
+                Some(WaitStatus::Signaled(sig)) => format!("Signal {sig}"),
+                _ => String::from("Done"),
+            },
+        }
+    }
+
+    pub(crate) fn resume(&mut self) -> std::io::Result<()> {
+        if let Some(pgid) = self.pgid {
+            process::resume(pgid)?;
+        }
+
+        for p in self.processes.iter_mut() {
+            if matches!(p.status, Some(WaitStatus::Stopped(_))) {
+                p.status = Some(WaitStatus::Continued);
+            }
+        }
+
+        self.notified = false;
+        Ok(())
+    }
+
+    pub(crate) fn poll(&mut self) -> std::io::Result<()> {
+        for p in self.processes.iter_mut() {
+            if p.status.is_none_or(|s| !s.is_finished())
+                && let Some(status) = process::wait(p.pid, false)?
+            {
+                p.status = Some(status);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn wait(&mut self) -> std::io::Result<()> {
+        for p in self.processes.iter_mut() {
+            while p.status.is_none_or(|s| !s.is_finished()) {
+                p.status = process::wait(p.pid, true)?;
+
+                if matches!(p.status, Some(WaitStatus::Stopped(_))) {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct JobTable {
+    jobs: Vec<Job>,
+    current: Option<usize>,
+    previous: Option<usize>,
+}
+
+impl JobTable {
+    pub(crate) fn insert(&mut self, mut job: Job) -> usize {
+        if job.id == 0 {
+            job.id = self.jobs.iter().map(|j| j.id).max().unwrap_or(0) + 1;
+        }
+
+        let id = job.id;
+        let index = self.jobs.partition_point(|j| j.id < id);
+        self.jobs.insert(index, job);
+        self.set_current(id);
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: usize) -> Option<Job> {
+        let index = self.jobs.iter().position(|j| j.id == id)?;
+        let job = self.jobs.remove(index);
+
+        if self.previous == Some(id) {
+            self.previous = None;
+        }
+
+        if self.current == Some(id) {
+            self.current = self.previous.take();
+        }
+
+        let mut ids = self.jobs.iter().rev().map(|j| j.id);
+
+        if self.current.is_none() {
+            self.current = ids.find(|id| Some(*id) != self.previous);
+        }
+
+        if self.previous.is_none() {
+            self.previous = ids.find(|id| Some(*id) != self.current);
+        }
+
+        Some(job)
+    }
+
+    pub(crate) fn set_current(&mut self, id: usize) {
+        if self.current != Some(id) {
+            self.previous = self.current;
+            self.current = Some(id);
+        }
+    }
+
+    pub(crate) fn get(&self, id: usize) -> Option<&Job> {
+        self.jobs.iter().find(|j| j.id == id)
+    }
+
+    pub(crate) fn get_mut(&mut self, id: usize) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|j| j.id == id)
+    }
+
+    pub(crate) fn ids(&self) -> Vec<usize> {
+        self.jobs.iter().map(|j| j.id).collect()
+    }
+
+    pub(crate) fn marker(&self, id: usize) -> char {
+        if self.current == Some(id) {
+            '+'
+        } else if self.previous == Some(id) {
+            '-'
+        } else {
+            ' '
+        }
+    }
+
+    pub(crate) fn display(&self, id: usize) -> Option<String> {
+        let job = self.get(id)?;
+
+        Some(format!(
+            "[{}]{}  {:<24}{}",
+            job.id,
+            self.marker(id),
+            job.state_description(),
+            job.command,
+        ))
+    }
+
+    pub(crate) fn find(&self, spec: Option<&str>) -> Result<usize, String> {
+        let spec = spec.unwrap_or("%+");
+
+        let Some(spec) = spec.strip_prefix('%') else {
+            return spec
+                .parse::<Pid>()
+                .ok()
+                .and_then(|pid| {
+                    self.jobs
+                        .iter()
+                        .find(|j| j.processes.iter().any(|p| p.pid == pid))
+                })
+                .map(|j| j.id)
+                .ok_or_else(|| format!("{spec}: no such job"));
+        };
+
+        let found = match spec {
+            "" | "+" | "%" => self.current,
+            "-" => self.previous,
+            _ => {
+                if let Ok(id) = spec.parse::<usize>() {
+                    self.get(id).map(|j| j.id)
+                } else {
+                    let matches = if let Some(substring) = spec.strip_prefix('?') {
+                        self.jobs
+                            .iter()
+                            .filter(|j| j.command.contains(substring))
+                            .collect::<Vec<_>>()
+                    } else {
+                        self.jobs
+                            .iter()
+                            .filter(|j| j.command.starts_with(spec))
+                            .collect::<Vec<_>>()
+                    };
+
+                    if matches.len() > 1 {
+                        return Err(format!("%{spec}: ambiguous job spec"));
+                    }
+
+                    matches.first().map(|j| j.id)
+                }
+            }
+        };
+
+        found.ok_or_else(|| format!("%{spec}: no such job"))
+    }
+}
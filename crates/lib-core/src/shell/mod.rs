@@ -1,4 +1,19 @@
+mod arithmetic;
 mod execution;
+mod flow;
+mod hash;
 mod instance;
+mod job;
+mod options;
+mod trap;
+mod variables;
 
+pub(crate) use execution::quote;
+pub(crate) use flow::Flow;
+pub(crate) use hash::CommandCache;
 pub use instance::Shell;
+pub(crate) use job::{Job, JobState, JobTable};
+pub(crate) use options::Options;
+pub use options::ShellOption;
+pub(crate) use trap::{Trap, Traps};
+pub(crate) use variables::{Attributes, Value, Variable, Variables};
@@ -0,0 +1,93 @@
+use std::collections::BTreeSet;
+
+use clap::ValueEnum;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum ShellOption {
+    /// Exit immediately when a command fails
+    Errexit,
+    /// Prevent output redirection from overwriting existing files
+    Noclobber,
+    /// Run the last stage of a pipeline in the current shell
+    Lastpipe,
+    /// Read commands without executing them
+    Noexec,
+    /// Treat expansion of unset parameters as an error
+    Nounset,
+    /// Return the status of the last pipeline stage to fail
+    Pipefail,
+    /// Print commands and their arguments as they are executed
+    Xtrace,
+}
+
+impl ShellOption {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Errexit => "errexit",
+            Self::Lastpipe => "lastpipe",
+            Self::Noclobber => "noclobber",
+            Self::Noexec => "noexec",
+            Self::Nounset => "nounset",
+            Self::Pipefail => "pipefail",
+            Self::Xtrace => "xtrace",
+        }
+    }
+
+    pub fn flag(&self) -> Option<char> {
+        match self {
+            Self::Errexit => Some('e'),
+            Self::Lastpipe => None,
+            Self::Noclobber => Some('C'),
+            Self::Noexec => Some('n'),
+            Self::Nounset => Some('u'),
+            Self::Pipefail => None,
+            Self::Xtrace => Some('x'),
+        }
+    }
+
+    /// Whether the option is listed and set by `set -o`, the others being `shopt` options.
+    pub fn is_set_option(&self) -> bool {
+        !matches!(self, Self::Lastpipe)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .find(|option| option.name() == name)
+            .copied()
+    }
+
+    pub fn from_flag(flag: char) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .find(|option| option.flag() == Some(flag))
+            .copied()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Options(BTreeSet<ShellOption>);
+
+impl Default for Options {
+    fn default() -> Self {
+        Self(BTreeSet::from([ShellOption::Lastpipe]))
+    }
+}
+
+impl Options {
+    pub(crate) fn get(&self, option: ShellOption) -> bool {
+        self.0.contains(&option)
+    }
+
+    pub(crate) fn set(&mut self, option: ShellOption, value: bool) {
+        if value {
+            self.0.insert(option);
+        } else {
+            self.0.remove(&option);
+        }
+    }
+
+    pub(crate) fn flags(&self) -> String {
+        self.0.iter().filter_map(|option| option.flag()).collect()
+    }
+}
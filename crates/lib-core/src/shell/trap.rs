@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use lib_os::signal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Trap {
+    Exit,
+    Signal(i32),
+    Debug,
+    Err,
+    Return,
+}
+
+impl Trap {
+    pub(crate) fn parse(spec: &str) -> Option<Self> {
+        match spec.to_uppercase().as_str() {
+            "0" | "EXIT" => Some(Self::Exit),
+            "DEBUG" => Some(Self::Debug),
+            "ERR" => Some(Self::Err),
+            "RETURN" => Some(Self::Return),
+            _ => signal::number(spec).map(Self::Signal),
+        }
+    }
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Self::Exit => String::from("EXIT"),
+            Self::Signal(sig) => signal::name(*sig)
+                .map(|name| format!("SIG{name}"))
+                .unwrap_or_else(|| sig.to_string()),
+            Self::Debug => String::from("DEBUG"),
+            Self::Err => String::from("ERR"),
+            Self::Return => String::from("RETURN"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Traps {
+    actions: BTreeMap<Trap, String>,
+    pub(crate) running: bool,
+}
+
+impl Traps {
+    pub(crate) fn get(&self, trap: Trap) -> Option<&str> {
+        self.actions.get(&trap).map(|action| action.as_str())
+    }
+
+    pub(crate) fn set(&mut self, trap: Trap, action: Option<String>) -> std::io::Result<()> {
+        if let Trap::Signal(sig) = trap {
+            match action.as_deref() {
+                None => signal::restore(sig)?,
+                Some("") => signal::ignore(sig)?,
+                Some(_) => signal::catch(sig)?,
+            }
+        }
+
+        match action {
+            Some(action) => self.actions.insert(trap, action),
+            None => self.actions.remove(&trap),
+        };
+
+        Ok(())
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Trap, &String)> {
+        self.actions.iter()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.actions.retain(|_, action| action.is_empty());
+        self.running = false;
+    }
+}
@@ -0,0 +1,699 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::shell::{arithmetic, quote};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Scalar(String),
+    Indexed(BTreeMap<usize, String>),
+    Associative(BTreeMap<String, String>),
+}
+
+impl Value {
+    pub(crate) fn indexed(values: impl IntoIterator<Item = String>) -> Self {
+        Self::Indexed(values.into_iter().enumerate().collect())
+    }
+
+    /// Looks up an element, treating a scalar as an array with a single element at index 0.
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        match self {
+            Self::Scalar(value) => (self.index(key)? == 0).then_some(value.as_str()),
+            Self::Indexed(map) => map.get(&self.index(key)?).map(String::as_str),
+            Self::Associative(map) => map.get(key).map(String::as_str),
+        }
+    }
+
+    /// The value seen when the variable is referenced without a subscript.
+    pub(crate) fn scalar(&self) -> Option<&str> {
+        self.get("0")
+    }
+
+    pub(crate) fn values(&self) -> Vec<&str> {
+        match self {
+            Self::Scalar(value) => vec![value],
+            Self::Indexed(map) => map.values().map(String::as_str).collect(),
+            Self::Associative(map) => map.values().map(String::as_str).collect(),
+        }
+    }
+
+    pub(crate) fn keys(&self) -> Vec<String> {
+        match self {
+            Self::Scalar(_) => vec![String::from("0")],
+            Self::Indexed(map) => map.keys().map(|i| i.to_string()).collect(),
+            Self::Associative(map) => map.keys().cloned().collect(),
+        }
+    }
+
+    /// Assigns an element, converting a scalar to an indexed array if needed. Returns false if
+    /// the subscript is out of range.
+    pub(crate) fn insert(&mut self, key: &str, value: String) -> bool {
+        if let Self::Scalar(scalar) = self
+            && key.trim().parse::<i64>().unwrap_or(0) == 0
+        {
+            *scalar = value;
+            return true;
+        }
+
+        let Some(index) = self.index(key) else {
+            if let Self::Associative(map) = self {
+                map.insert(key.to_string(), value);
+                return true;
+            }
+
+            return false;
+        };
+
+        if let Self::Scalar(scalar) = self {
+            *self = Self::Indexed(BTreeMap::from([(0, std::mem::take(scalar))]));
+        }
+
+        if let Self::Indexed(map) = self {
+            map.insert(index, value);
+        }
+
+        true
+    }
+
+    /// Appends elements after the highest index, converting a scalar to an indexed array.
+    pub(crate) fn push(&mut self, values: impl IntoIterator<Item = String>) {
+        for value in values {
+            let next = match self {
+                Self::Scalar(_) => 1,
+                Self::Indexed(map) => map.keys().next_back().map_or(0, |i| i + 1),
+                Self::Associative(_) => return,
+            };
+
+            self.insert(&next.to_string(), value);
+        }
+    }
+
+    /// Removes an element, returning false if the subscript is out of range.
+    pub(crate) fn remove(&mut self, key: &str) -> bool {
+        match self {
+            Self::Associative(map) => {
+                map.remove(key);
+                true
+            }
+            _ => match self.index(key) {
+                Some(index) => {
+                    match self {
+                        Self::Scalar(scalar) if index == 0 => {
+                            *self = Self::Indexed(BTreeMap::new())
+                        }
+                        Self::Indexed(map) => _ = map.remove(&index),
+                        _ => {}
+                    }
+
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Resolves an indexed subscript, counting negative indices back from the end.
+    fn index(&self, key: &str) -> Option<usize> {
+        let len = match self {
+            Self::Scalar(_) => 1,
+            Self::Indexed(map) => map.keys().next_back().map_or(0, |i| i + 1),
+            Self::Associative(_) => return None,
+        };
+
+        match key.trim().parse::<i64>().unwrap_or(0) {
+            index if index < 0 => usize::try_from(len as i64 + index).ok(),
+            index => usize::try_from(index).ok(),
+        }
+    }
+
+    /// Formats the value as an assignment to `name` that the shell can read back in.
+    pub(crate) fn assignment(&self, name: &str) -> String {
+        let elements = |elements: Vec<(String, &String)>| {
+            elements
+                .into_iter()
+                .map(|(key, value)| format!("[{key}]={}", double_quote(value)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        match self {
+            Self::Scalar(value) => format!("{name}={}", quote(value)),
+            Self::Indexed(map) => {
+                let map = map.iter().map(|(i, v)| (i.to_string(), v)).collect();
+                format!("{name}=({})", elements(map))
+            }
+            Self::Associative(map) => {
+                let map = map.iter().map(|(k, v)| (quote(k), v)).collect();
+                format!("{name}=({})", elements(map))
+            }
+        }
+    }
+}
+
+/// Double quotes `value` as bash does when printing arrays and declarations.
+fn double_quote(value: &str) -> String {
+    let mut quoted = String::from('"');
+
+    for c in value.chars() {
+        if "\\\"$`".contains(c) {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Attributes {
+    pub(crate) exported: bool,
+    pub(crate) readonly: bool,
+    pub(crate) integer: bool,
+    pub(crate) lowercase: bool,
+    pub(crate) uppercase: bool,
+    pub(crate) nameref: bool,
+}
+
+impl Attributes {
+    /// Adds the attributes set in `other`, a case conversion replacing any previous one.
+    pub(crate) fn merge(&mut self, other: Attributes) {
+        self.exported |= other.exported;
+        self.readonly |= other.readonly;
+        self.integer |= other.integer;
+        self.nameref |= other.nameref;
+
+        if other.lowercase || other.uppercase {
+            self.lowercase = other.lowercase;
+            self.uppercase = other.uppercase;
+        }
+    }
+
+    /// Whether every attribute set in `other` is also set here.
+    pub(crate) fn contains(&self, other: Attributes) -> bool {
+        let mut merged = *self;
+        merged.merge(other);
+        merged == *self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Variable {
+    pub(crate) value: Option<Value>,
+    pub(crate) attributes: Attributes,
+}
+
+impl Variable {
+    /// Replaces the value, except that a scalar assigned to an array sets its first element.
+    fn assign(&mut self, value: Value) {
+        match (&mut self.value, value) {
+            (Some(current), Value::Scalar(value)) => _ = current.insert("0", value),
+            (_, value) => self.value = Some(value),
+        }
+    }
+
+    /// The attribute letters in the order `declare -p` prints them.
+    pub(crate) fn flags(&self) -> String {
+        let attributes = &self.attributes;
+
+        [
+            (matches!(self.value, Some(Value::Indexed(_))), 'a'),
+            (matches!(self.value, Some(Value::Associative(_))), 'A'),
+            (attributes.integer, 'i'),
+            (attributes.nameref, 'n'),
+            (attributes.readonly, 'r'),
+            (attributes.exported, 'x'),
+            (attributes.lowercase, 'l'),
+            (attributes.uppercase, 'u'),
+        ]
+        .into_iter()
+        .filter_map(|(set, flag)| set.then_some(flag))
+        .collect()
+    }
+
+    /// Formats the variable as a `declare` command that recreates it.
+    pub(crate) fn declaration(&self, name: &str) -> String {
+        let flags = match self.flags() {
+            flags if flags.is_empty() => String::from("-"),
+            flags => flags,
+        };
+
+        match &self.value {
+            Some(Value::Scalar(value)) => {
+                format!("declare -{flags} {name}={}", double_quote(value))
+            }
+            Some(value) => format!("declare -{flags} {}", value.assignment(name)),
+            None => format!("declare -{flags} {name}"),
+        }
+    }
+}
+
+/// Maximum length of a chain of namerefs before the last one is taken as the variable itself.
+const MAX_NAMEREFS: usize = 8;
+
+/// Shell variables held in a stack of scopes. Lookups walk from the innermost function scope out
+/// to the globals, so callees see their callers' locals.
+#[derive(Debug)]
+pub(crate) struct Variables {
+    scopes: Vec<HashMap<String, Variable>>,
+}
+
+impl Default for Variables {
+    fn default() -> Self {
+        let globals = std::env::vars()
+            .map(|(name, value)| {
+                let variable = Variable {
+                    value: Some(Value::Scalar(value)),
+                    attributes: Attributes {
+                        exported: true,
+                        ..Default::default()
+                    },
+                };
+
+                (name, variable)
+            })
+            .collect();
+
+        Self {
+            scopes: vec![globals],
+        }
+    }
+}
+
+impl Variables {
+    /// Finds the variable called `name` without following namerefs.
+    pub(crate) fn lookup(&self, name: &str) -> Option<&Variable> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn lookup_mut(&mut self, name: &str) -> Option<&mut Variable> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+    }
+
+    /// Follows namerefs from `name` to the name of the variable they refer to.
+    pub(crate) fn resolve<'a>(&'a self, name: &'a str) -> &'a str {
+        let mut name = name;
+
+        for _ in 0..MAX_NAMEREFS {
+            match self.lookup(name) {
+                Some(Variable {
+                    value: Some(Value::Scalar(target)),
+                    attributes,
+                }) if attributes.nameref && !target.is_empty() => name = target,
+                _ => break,
+            }
+        }
+
+        name
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Variable> {
+        self.lookup(self.resolve(name))
+    }
+
+    pub(crate) fn evaluate(&self, expression: &str) -> Result<i64, String> {
+        arithmetic::evaluate(expression, &|name| {
+            self.get(name)?.value.as_ref()?.scalar().map(String::from)
+        })
+    }
+
+    /// Applies the integer and case conversion attributes to a value being assigned.
+    fn convert(&self, attributes: Attributes, value: String) -> Result<String, String> {
+        let value = match attributes.integer {
+            true => self.evaluate(&value)?.to_string(),
+            false => value,
+        };
+
+        Ok(if attributes.lowercase {
+            value.to_lowercase()
+        } else if attributes.uppercase {
+            value.to_uppercase()
+        } else {
+            value
+        })
+    }
+
+    fn convert_value(&self, attributes: Attributes, value: Value) -> Result<Value, String> {
+        Ok(match value {
+            Value::Scalar(value) => Value::Scalar(self.convert(attributes, value)?),
+            Value::Indexed(map) => Value::Indexed(
+                map.into_iter()
+                    .map(|(i, value)| Ok((i, self.convert(attributes, value)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            Value::Associative(map) => Value::Associative(
+                map.into_iter()
+                    .map(|(key, value)| Ok((key, self.convert(attributes, value)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+        })
+    }
+
+    /// Resolves `name` for assignment, failing if the variable is readonly.
+    fn writable(&self, name: &str) -> Result<(String, Attributes), String> {
+        let name = self.resolve(name);
+        let attributes = self.lookup(name).map(|v| v.attributes).unwrap_or_default();
+
+        match attributes.readonly {
+            true => Err(String::from("readonly variable")),
+            false => Ok((name.to_string(), attributes)),
+        }
+    }
+
+    /// Assigns to the innermost visible variable called `name`, creating a global if none exists.
+    /// Assigning a scalar to an array sets its first element.
+    pub(crate) fn set(&mut self, name: &str, value: Value) -> Result<(), String> {
+        let (name, attributes) = self.writable(name)?;
+        let value = self.convert_value(attributes, value)?;
+
+        match self.lookup_mut(&name) {
+            Some(variable) => variable.assign(value),
+            None => {
+                let variable = Variable {
+                    value: Some(value),
+                    ..Default::default()
+                };
+
+                self.scopes[0].insert(name, variable);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Assigns one element of an array, creating an indexed array if `name` is unset.
+    pub(crate) fn set_element(
+        &mut self,
+        name: &str,
+        key: &str,
+        value: String,
+    ) -> Result<(), String> {
+        let (name, attributes) = self.writable(name)?;
+        let value = self.convert(attributes, value)?;
+
+        let inserted = match self.lookup_mut(&name) {
+            Some(variable) => variable
+                .value
+                .get_or_insert_with(|| Value::Indexed(BTreeMap::new()))
+                .insert(key, value),
+            None => {
+                let mut array = Value::Indexed(BTreeMap::new());
+                let inserted = array.insert(key, value);
+
+                if inserted {
+                    let variable = Variable {
+                        value: Some(array),
+                        ..Default::default()
+                    };
+
+                    self.scopes[0].insert(name, variable);
+                }
+
+                inserted
+            }
+        };
+
+        match inserted {
+            true => Ok(()),
+            false => Err(String::from("bad array subscript")),
+        }
+    }
+
+    /// Declares `name` in the innermost scope if `local`, shadowing any variable of the same name
+    /// outside it, or otherwise in the global scope. The attributes are added to any it already has.
+    pub(crate) fn declare(
+        &mut self,
+        name: &str,
+        value: Option<Value>,
+        attributes: Attributes,
+        local: bool,
+    ) -> Result<(), String> {
+        let name = match local || attributes.nameref {
+            true => name.to_string(),
+            false => self.resolve(name).to_string(),
+        };
+
+        let index = self.scope_index(local);
+        let current = self.scopes[index].get(&name);
+        let mut merged = current.map(|v| v.attributes).unwrap_or_default();
+
+        if merged.readonly && value.is_some() {
+            return Err(String::from("readonly variable"));
+        }
+
+        merged.merge(Attributes {
+            readonly: false,
+            ..attributes
+        });
+
+        let value = match value {
+            Some(value) => Some(self.convert_value(merged, value)?),
+            None => None,
+        };
+
+        let variable = self.scopes[index].entry(name).or_default();
+
+        if let Some(value) = value {
+            variable.assign(value);
+        }
+
+        merged.readonly |= attributes.readonly;
+        variable.attributes = merged;
+        Ok(())
+    }
+
+    /// Declares `name` as an indexed or associative array, keeping the elements of an existing
+    /// array of the same kind and converting a scalar to an indexed array.
+    pub(crate) fn declare_array(
+        &mut self,
+        name: &str,
+        associative: bool,
+        local: bool,
+    ) -> Result<(), String> {
+        let index = self.scope_index(local);
+        let variable = self.scopes[index].entry(name.to_string()).or_default();
+
+        let value = match (variable.value.take(), associative) {
+            (None, false) => Value::Indexed(BTreeMap::new()),
+            (None, true) => Value::Associative(BTreeMap::new()),
+            (Some(Value::Scalar(value)), false) => Value::indexed([value]),
+            (Some(value @ Value::Indexed(_)), false) => value,
+            (Some(value @ Value::Associative(_)), true) => value,
+            (value, associative) => {
+                variable.value = value;
+
+                return Err(String::from(match associative {
+                    true => "cannot convert indexed to associative array",
+                    false => "cannot convert associative to indexed array",
+                }));
+            }
+        };
+
+        variable.value = Some(value);
+        Ok(())
+    }
+
+    fn scope_index(&self, local: bool) -> usize {
+        match local {
+            true => self.scopes.len() - 1,
+            false => 0,
+        }
+    }
+
+    /// Unsets the innermost visible variable called `name`. A local of the current function stays
+    /// declared but unset until the function returns, as in bash.
+    pub(crate) fn unset(&mut self, name: &str) -> Result<(), String> {
+        let name = self.resolve(name).to_string();
+        let depth = self.scopes.len() - 1;
+
+        let Some((i, scope)) = self
+            .scopes
+            .iter_mut()
+            .enumerate()
+            .rev()
+            .find(|(_, scope)| scope.contains_key(&name))
+        else {
+            return Ok(());
+        };
+
+        match scope.get_mut(&name) {
+            Some(variable) if variable.attributes.readonly => {
+                return Err(String::from("cannot unset: readonly variable"));
+            }
+            Some(variable) if i == depth && i > 0 => variable.value = None,
+            _ => _ = scope.remove(&name),
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn unset_element(&mut self, name: &str, key: &str) -> Result<(), String> {
+        let (name, _) = self.writable(name)?;
+
+        let removed = match self.lookup_mut(&name) {
+            Some(Variable {
+                value: Some(current),
+                ..
+            }) => current.remove(key),
+            _ => true,
+        };
+
+        match removed {
+            true => Ok(()),
+            false => Err(String::from("bad array subscript")),
+        }
+    }
+
+    pub(crate) fn is_local_scope(&self) -> bool {
+        self.scopes.len() > 1
+    }
+
+    pub(crate) fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// Discards the innermost function scope.
+    pub(crate) fn pop_scope(&mut self) {
+        if self.is_local_scope() {
+            self.scopes.pop();
+        }
+    }
+
+    pub(crate) fn locals(&self) -> BTreeMap<&String, &Variable> {
+        match self.scopes.last() {
+            Some(scope) if self.is_local_scope() => scope.iter().collect(),
+            _ => BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> BTreeMap<&String, &Variable> {
+        let mut visible = BTreeMap::new();
+
+        for scope in &self.scopes {
+            visible.extend(scope.iter());
+        }
+
+        visible
+    }
+
+    /// The visible exported variables with scalar values, which make up the environment of the
+    /// commands the shell runs.
+    pub(crate) fn environment(&self) -> BTreeMap<&str, &str> {
+        self.iter()
+            .into_iter()
+            .filter_map(|(name, variable)| match variable {
+                Variable {
+                    value: Some(Value::Scalar(value)),
+                    attributes,
+                } if attributes.exported => Some((name.as_str(), value.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(value: &str) -> Option<Value> {
+        Some(Value::Scalar(value.to_string()))
+    }
+
+    fn exported() -> Attributes {
+        Attributes {
+            exported: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn locals_are_visible_to_callees() {
+        let mut variables = Variables::default();
+        variables
+            .set("scope_callee", Value::Scalar("global".into()))
+            .unwrap();
+
+        variables.push_scope();
+        variables
+            .declare(
+                "scope_callee",
+                scalar("caller"),
+                Attributes::default(),
+                true,
+            )
+            .unwrap();
+        variables.push_scope();
+
+        assert_eq!(
+            variables.get("scope_callee").unwrap().value,
+            scalar("caller")
+        );
+        assert!(variables.locals().is_empty());
+    }
+
+    #[test]
+    fn pop_scope_restores_values() {
+        let mut variables = Variables::default();
+        variables
+            .set("scope_restore", Value::Scalar("global".into()))
+            .unwrap();
+
+        variables.push_scope();
+        variables
+            .declare(
+                "scope_restore",
+                scalar("local"),
+                Attributes::default(),
+                true,
+            )
+            .unwrap();
+        variables
+            .declare("scope_new", scalar("local"), Attributes::default(), true)
+            .unwrap();
+
+        variables.pop_scope();
+
+        assert_eq!(
+            variables.get("scope_restore").unwrap().value,
+            scalar("global")
+        );
+        assert!(variables.get("scope_new").is_none());
+
+        variables.pop_scope();
+        assert!(variables.get("scope_restore").is_some());
+    }
+
+    #[test]
+    fn environment_follows_exported_locals() {
+        let mut variables = Variables::default();
+        variables
+            .declare("scope_shadowed", scalar("global"), exported(), false)
+            .unwrap();
+
+        variables.push_scope();
+        variables
+            .declare("scope_shadowed", scalar("local"), exported(), true)
+            .unwrap();
+        variables
+            .declare("scope_local", scalar("local"), exported(), true)
+            .unwrap();
+        variables
+            .declare("scope_hidden", scalar("local"), Attributes::default(), true)
+            .unwrap();
+
+        let env = variables.environment();
+        assert_eq!(env.get("scope_shadowed").copied(), Some("local"));
+        assert_eq!(env.get("scope_local").copied(), Some("local"));
+        assert!(!env.contains_key("scope_hidden"));
+
+        variables.pop_scope();
+
+        let env = variables.environment();
+        assert_eq!(env.get("scope_shadowed").copied(), Some("global"));
+        assert!(!env.contains_key("scope_local"));
+    }
+}
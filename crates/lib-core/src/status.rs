@@ -0,0 +1,97 @@
+use lib_os::process::WaitStatus;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExitStatus(u8);
+
+impl ExitStatus {
+    pub const SUCCESS: Self = Self(0);
+    pub const FAILURE: Self = Self(1);
+    pub const MISUSE: Self = Self(2);
+    pub const NOT_EXECUTABLE: Self = Self(126);
+    pub const NOT_FOUND: Self = Self(127);
+    pub const INTERRUPTED: Self = Self(130);
+
+    pub const fn new(code: u8) -> Self {
+        Self(code)
+    }
+
+    pub const fn from_signal(signal: i32) -> Self {
+        Self((128 + signal) as u8)
+    }
+
+    pub const fn code(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn signal(&self) -> Option<i32> {
+        if self.0 > 128 {
+            Some(self.0 as i32 - 128)
+        } else {
+            None
+        }
+    }
+
+    pub const fn is_success(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn is_failure(&self) -> bool {
+        !self.is_success()
+    }
+}
+
+impl From<u8> for ExitStatus {
+    fn from(code: u8) -> Self {
+        Self(code)
+    }
+}
+
+impl From<i32> for ExitStatus {
+    fn from(code: i32) -> Self {
+        Self(code as u8)
+    }
+}
+
+impl From<bool> for ExitStatus {
+    fn from(success: bool) -> Self {
+        if success {
+            Self::SUCCESS
+        } else {
+            Self::FAILURE
+        }
+    }
+}
+
+impl From<WaitStatus> for ExitStatus {
+    fn from(status: WaitStatus) -> Self {
+        match status {
+            WaitStatus::Exited(code) => Self::from(code),
+            WaitStatus::Signaled(signal) | WaitStatus::Stopped(signal) => Self::from_signal(signal),
+            WaitStatus::Continued => Self::SUCCESS,
+        }
+    }
+}
+
+impl From<ExitStatus> for i32 {
+    fn from(status: ExitStatus) -> Self {
+        status.0 as i32
+    }
+}
+
+impl From<ExitStatus> for std::process::ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        Self::from(status.0)
+    }
+}
+
+impl std::process::Termination for ExitStatus {
+    fn report(self) -> std::process::ExitCode {
+        self.into()
+    }
+}
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use nix::unistd::{self, AccessFlags};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+pub fn has_access<P: AsRef<Path>>(path: P, access: Access) -> bool {
+    let flags = match access {
+        Access::Read => AccessFlags::R_OK,
+        Access::Write => AccessFlags::W_OK,
+        Access::Execute => AccessFlags::X_OK,
+    };
+
+    unistd::access(path.as_ref(), flags).is_ok()
+}
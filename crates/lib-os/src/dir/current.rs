@@ -1,3 +1,8 @@
+use std::{
+    os::unix::fs::MetadataExt,
+    path::{Component, Path, PathBuf},
+};
+
 pub fn current() -> String {
     format!(
         "{}",
@@ -6,3 +11,39 @@ pub fn current() -> String {
             .to_string_lossy()
     )
 }
+
+/// Returns `$PWD` if it is a normalized absolute path to the current directory, otherwise the
+/// physical path.
+pub fn current_logical() -> String {
+    if let Ok(pwd) = std::env::var("PWD")
+        && Path::new(&pwd).is_absolute()
+        && normalize(&pwd) == Path::new(&pwd)
+        && is_same_file(&pwd, ".")
+    {
+        pwd
+    } else {
+        current()
+    }
+}
+
+/// Lexically resolves `.` and `..` components of an absolute path without touching the filesystem.
+pub fn normalize<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut normal = PathBuf::from("/");
+
+    for component in path.as_ref().components() {
+        match component {
+            Component::ParentDir => _ = normal.pop(),
+            Component::Normal(name) => normal.push(name),
+            _ => {}
+        }
+    }
+
+    normal
+}
+
+pub fn is_same_file<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) -> bool {
+    match (a.as_ref().metadata(), b.as_ref().metadata()) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
@@ -1,21 +1,51 @@
-use std::path::{Path, PathBuf};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_PATH: &str = "/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin";
 
-#[cfg(not(target_os = "windows"))]
 pub fn find_on_path<P: AsRef<Path>>(keyword: P) -> Option<PathBuf> {
-    std::env::split_paths(&std::env::var_os("PATH")?)
-        .map(|dir| dir.join(&keyword))
-        .find(|path| path.is_file())
+    search_path(&std::env::var_os("PATH")?, keyword).next()
+}
+
+pub fn find_all_on_path<P: AsRef<Path>>(keyword: P) -> Vec<PathBuf> {
+    match std::env::var_os("PATH") {
+        Some(paths) => search_path(&paths, keyword).collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn search_path<P: AsRef<Path>>(paths: &OsStr, keyword: P) -> impl Iterator<Item = PathBuf> {
+    std::env::split_paths(paths)
+        .map(move |dir| dir.join(&keyword))
+        .filter(|path| is_executable(path))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_executable<P: AsRef<Path>>(path: P) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.as_ref()
+        .metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
 }
 
 #[cfg(target_os = "windows")]
 use std::{slice::Iter, sync::OnceLock};
 
 #[cfg(target_os = "windows")]
-pub fn find_on_path<P: AsRef<Path>>(keyword: P) -> Option<PathBuf> {
-    std::env::split_paths(&std::env::var_os("PATH")?)
-        .map(|dir| dir.join(&keyword))
+pub fn search_path<P: AsRef<Path>>(paths: &OsStr, keyword: P) -> impl Iterator<Item = PathBuf> {
+    std::env::split_paths(paths)
+        .map(move |dir| dir.join(&keyword))
         .flat_map(|path| path_ext().map(move |ext| path.clone().with_extension(ext)))
-        .find(|path| path.is_file())
+        .filter(|path| is_executable(path))
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_executable<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref().is_file()
 }
 
 #[cfg(target_os = "windows")]
@@ -1,7 +1,9 @@
-mod home;
+mod access;
 mod current;
 mod find;
+mod home;
 
+pub use access::{Access, has_access};
+pub use current::{current, current_logical, is_same_file, normalize};
+pub use find::{DEFAULT_PATH, find_all_on_path, find_on_path, is_executable, search_path};
 pub use home::{home, my_home};
-pub use current::current;
-pub use find::find_on_path;
@@ -1,49 +1,121 @@
-use std::io::{Read, Write};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    os::{
+        fd::{AsRawFd, OwnedFd, RawFd},
+        unix::process::CommandExt,
+    },
+    process::{Command, Stdio},
+};
 
-use super::{input::Input, output::Output};
+use nix::libc;
+
+use super::{handle::Handle, input::Input, output::Output};
 
 #[derive(Debug)]
 pub struct Context {
-    pub input: Input,
-    pub output: Output,
-    pub error: Output,
+    fds: BTreeMap<RawFd, Handle>,
 }
 
 impl Default for Context {
     fn default() -> Self {
-        Self {
-            input: Input::Stdin(std::io::stdin()),
-            output: Output::Stdout(std::io::stdout()),
-            error: Output::Stderr(std::io::stderr()),
-        }
+        let mut ctx = Self {
+            fds: BTreeMap::new(),
+        };
+
+        ctx.set(0, Input::Stdin(std::io::stdin()));
+        ctx.set(1, Output::Stdout(std::io::stdout()));
+        ctx.set(2, Output::Stderr(std::io::stderr()));
+        ctx
     }
 }
 
 impl Context {
     pub fn try_clone(&self) -> std::io::Result<Self> {
-        Ok(Self {
-            input: self.input.try_clone()?,
-            output: self.output.try_clone()?,
-            error: self.error.try_clone()?,
-        })
+        let fds = self
+            .fds
+            .iter()
+            .map(|(fd, handle)| Ok((*fd, handle.try_clone()?)))
+            .collect::<std::io::Result<_>>()?;
+
+        Ok(Self { fds })
     }
 
     pub fn _null() -> Self {
-        Self {
-            input: Input::Null,
-            output: Output::Null,
-            error: Output::Null,
-        }
+        let mut ctx = Self {
+            fds: BTreeMap::new(),
+        };
+
+        ctx.set(0, Input::Null);
+        ctx.set(1, Output::Null);
+        ctx.set(2, Output::Null);
+        ctx
     }
 
     pub fn _read(&mut self) -> std::io::Result<String> {
         let mut buffer = String::new();
-        self.input.read_to_string(&mut buffer)?;
+        self.input().read_to_string(&mut buffer)?;
         Ok(buffer)
     }
 
+    pub fn get(&mut self, fd: RawFd) -> &mut Handle {
+        self.fds.entry(fd).or_default()
+    }
+
+    pub fn set<H: Into<Handle>>(&mut self, fd: RawFd, handle: H) {
+        self.fds.insert(fd, handle.into());
+    }
+
+    pub fn close(&mut self, fd: RawFd) {
+        self.fds.insert(fd, Handle::Closed);
+    }
+
+    pub fn dup(&mut self, from: RawFd, to: RawFd) -> std::io::Result<()> {
+        if from != to {
+            let handle = self.get(from).try_clone()?;
+
+            if handle.is_closed() {
+                return Err(std::io::Error::from_raw_os_error(libc::EBADF));
+            }
+
+            self.set(to, handle);
+        }
+
+        Ok(())
+    }
+
+    pub fn fds(&self) -> Vec<RawFd> {
+        self.fds
+            .iter()
+            .filter(|(_, handle)| !handle.is_closed())
+            .map(|(fd, _)| *fd)
+            .collect()
+    }
+
+    pub fn input(&mut self) -> &mut Handle {
+        self.get(0)
+    }
+
+    pub fn output(&mut self) -> &mut Handle {
+        self.get(1)
+    }
+
+    pub fn error(&mut self) -> &mut Handle {
+        self.get(2)
+    }
+
+    pub fn flush(&mut self) {
+        for handle in self.fds.values_mut() {
+            let _ = handle.flush();
+        }
+    }
+
+    pub fn write<S: AsRef<[u8]>>(&mut self, fd: RawFd, msg: S) -> std::io::Result<()> {
+        self.get(fd).write_all(msg.as_ref())
+    }
+
     pub fn print<S: AsRef<str>>(&mut self, msg: S) {
-        let _ = self.output.write_all(msg.as_ref().as_bytes());
+        let _ = self.write(1, msg.as_ref());
     }
 
     pub fn println<S: AsRef<str>>(&mut self, msg: S) {
@@ -51,10 +123,70 @@ impl Context {
     }
 
     pub fn eprint<S: AsRef<str>>(&mut self, msg: S) {
-        let _ = self.error.write_all(msg.as_ref().as_bytes());
+        let _ = self.write(2, msg.as_ref());
     }
 
     pub fn eprintln<S: AsRef<str>>(&mut self, msg: S) {
         self.eprint(format!("{}\n", msg.as_ref()));
     }
+
+    pub fn attach(&self, command: &mut Command) -> std::io::Result<()> {
+        let mut inherited = Vec::new();
+        let mut closed = Vec::new();
+
+        for (fd, handle) in &self.fds {
+            match (*fd, handle.try_clone_fd()?) {
+                (0, Some(owned)) => _ = command.stdin(Stdio::from(owned)),
+                (1, Some(owned)) => _ = command.stdout(Stdio::from(owned)),
+                (2, Some(owned)) => _ = command.stderr(Stdio::from(owned)),
+                (fd, Some(owned)) => inherited.push((fd, owned)),
+                (fd, None) => closed.push(fd),
+            }
+        }
+
+        if inherited.is_empty() && closed.is_empty() {
+            return Ok(());
+        }
+
+        let lowest = inherited.iter().map(|(fd, _)| *fd).max().unwrap_or(2) + 1;
+        let mut moved = Vec::with_capacity(inherited.len());
+
+        unsafe {
+            command.pre_exec(move || map_descriptors(&inherited, &closed, lowest, &mut moved));
+        }
+
+        Ok(())
+    }
+}
+
+fn map_descriptors(
+    inherited: &[(RawFd, OwnedFd)],
+    closed: &[RawFd],
+    lowest: RawFd,
+    moved: &mut Vec<RawFd>,
+) -> std::io::Result<()> {
+    let check = |result: libc::c_int| {
+        if result < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(result)
+        }
+    };
+
+    moved.clear();
+
+    for (_, owned) in inherited {
+        let fd = unsafe { libc::fcntl(owned.as_raw_fd(), libc::F_DUPFD_CLOEXEC, lowest) };
+        moved.push(check(fd)?);
+    }
+
+    for ((target, _), fd) in inherited.iter().zip(moved.iter()) {
+        check(unsafe { libc::dup2(*fd, *target) })?;
+    }
+
+    for fd in closed {
+        unsafe { libc::close(*fd) };
+    }
+
+    Ok(())
 }
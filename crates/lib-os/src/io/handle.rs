@@ -0,0 +1,117 @@
+use std::{
+    io::{IsTerminal, Read, Write},
+    os::fd::{AsFd, BorrowedFd, OwnedFd},
+};
+
+use super::{input::Input, output::Output};
+
+#[derive(Debug, Default)]
+pub enum Handle {
+    #[default]
+    Closed,
+    Input(Input),
+    Output(Output),
+}
+
+impl From<Input> for Handle {
+    fn from(input: Input) -> Self {
+        Self::Input(input)
+    }
+}
+
+impl From<Output> for Handle {
+    fn from(output: Output) -> Self {
+        Self::Output(output)
+    }
+}
+
+impl From<os_pipe::PipeReader> for Handle {
+    fn from(input: os_pipe::PipeReader) -> Self {
+        Self::Input(input.into())
+    }
+}
+
+impl From<os_pipe::PipeWriter> for Handle {
+    fn from(output: os_pipe::PipeWriter) -> Self {
+        Self::Output(output.into())
+    }
+}
+
+fn bad_descriptor() -> std::io::Error {
+    std::io::Error::from_raw_os_error(nix::libc::EBADF)
+}
+
+impl Read for Handle {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        match *self {
+            Self::Input(ref mut input) => input.read(buffer),
+            Self::Output(Output::File(ref mut file)) => file.read(buffer),
+            _ => Err(bad_descriptor()),
+        }
+    }
+}
+
+impl Write for Handle {
+    fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+        match *self {
+            Self::Output(ref mut output) => output.write(buffer),
+            Self::Input(Input::File(ref mut file)) => file.write(buffer),
+            _ => Err(bad_descriptor()),
+        }
+    }
+
+    fn write_all(&mut self, buffer: &[u8]) -> std::io::Result<()> {
+        match *self {
+            Self::Output(ref mut output) => output.write_all(buffer),
+            Self::Input(Input::File(ref mut file)) => file.write_all(buffer),
+            _ => Err(bad_descriptor()),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match *self {
+            Self::Output(ref mut output) => output.flush(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Handle {
+    pub fn try_clone(&self) -> std::io::Result<Self> {
+        match *self {
+            Self::Closed => Ok(Self::Closed),
+            Self::Input(ref input) => Ok(Self::Input(input.try_clone()?)),
+            Self::Output(ref output) => Ok(Self::Output(output.try_clone()?)),
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed)
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        match *self {
+            Self::Output(Output::Stdout(ref stream)) => stream.is_terminal(),
+            Self::Output(Output::Stderr(ref stream)) => stream.is_terminal(),
+            _ => self.as_fd().is_some_and(|fd| fd.is_terminal()),
+        }
+    }
+
+    pub(crate) fn as_fd(&self) -> Option<BorrowedFd<'_>> {
+        match *self {
+            Self::Input(Input::Pipe(ref pipe)) => Some(pipe.as_fd()),
+            Self::Input(Input::File(ref file)) => Some(file.as_fd()),
+            Self::Input(Input::Stdin(ref stream)) => Some(stream.as_fd()),
+            Self::Output(Output::File(ref file)) => Some(file.as_fd()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn try_clone_fd(&self) -> std::io::Result<Option<OwnedFd>> {
+        match *self {
+            Self::Closed => Ok(None),
+            Self::Input(ref input) => input.try_clone_fd().map(Some),
+            Self::Output(ref output) => output.try_clone_fd().map(Some),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::{fs, process::Stdio};
+use std::{
+    fs,
+    os::fd::{AsFd, OwnedFd},
+};
 
 #[derive(Debug)]
 pub enum Input {
@@ -26,17 +29,6 @@ impl From<os_pipe::PipeReader> for Input {
     }
 }
 
-impl From<Input> for Stdio {
-    fn from(input: Input) -> Stdio {
-        match input {
-            Input::Null => Stdio::null(),
-            Input::Pipe(pipe) => pipe.into(),
-            Input::File(file) => file.into(),
-            Input::Stdin(_) => Stdio::inherit(),
-        }
-    }
-}
-
 impl std::io::Read for Input {
     fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
         match *self {
@@ -57,4 +49,13 @@ impl Input {
             Self::Stdin(_) => Ok(Self::Stdin(std::io::stdin())),
         }
     }
+
+    pub(crate) fn try_clone_fd(&self) -> std::io::Result<OwnedFd> {
+        match *self {
+            Self::Null => Ok(fs::File::open("/dev/null")?.into()),
+            Self::Pipe(ref pipe) => pipe.as_fd().try_clone_to_owned(),
+            Self::File(ref file) => file.as_fd().try_clone_to_owned(),
+            Self::Stdin(ref stream) => stream.as_fd().try_clone_to_owned(),
+        }
+    }
 }
@@ -1,8 +1,18 @@
 mod context;
+mod handle;
 mod input;
 mod output;
+mod reader;
 
 pub use context::Context;
+pub use handle::Handle;
 pub use input::Input;
-pub use output::Output;
 pub use os_pipe::pipe;
+pub use output::Output;
+pub use reader::ByteReader;
+
+/// Closes a descriptor that a forked child inherited but must not hold open, such as the read end
+/// of the pipe it writes to.
+pub fn close(fd: std::os::fd::RawFd) {
+    _ = nix::unistd::close(fd);
+}
@@ -1,4 +1,7 @@
-use std::{fs, process::Stdio};
+use std::{
+    fs,
+    os::fd::{AsFd, OwnedFd},
+};
 
 #[derive(Debug)]
 pub enum Output {
@@ -33,18 +36,6 @@ impl From<os_pipe::PipeWriter> for Output {
     }
 }
 
-impl From<Output> for Stdio {
-    fn from(output: Output) -> Stdio {
-        match output {
-            Output::Null => Stdio::null(),
-            Output::Pipe(pipe) => pipe.into(),
-            Output::File(file) => file.into(),
-            Output::Stdout(_) => Stdio::inherit(),
-            Output::Stderr(_) => Stdio::inherit(),
-        }
-    }
-}
-
 impl std::io::Write for Output {
     fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
         match *self {
@@ -87,4 +78,14 @@ impl Output {
             Self::Stderr(_) => Ok(Self::Stderr(std::io::stderr())),
         }
     }
+
+    pub(crate) fn try_clone_fd(&self) -> std::io::Result<OwnedFd> {
+        match *self {
+            Self::Null => Ok(fs::OpenOptions::new().write(true).open("/dev/null")?.into()),
+            Self::Pipe(ref pipe) => pipe.as_fd().try_clone_to_owned(),
+            Self::File(ref file) => file.as_fd().try_clone_to_owned(),
+            Self::Stdout(ref stream) => stream.as_fd().try_clone_to_owned(),
+            Self::Stderr(ref stream) => stream.as_fd().try_clone_to_owned(),
+        }
+    }
 }
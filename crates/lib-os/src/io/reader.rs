@@ -0,0 +1,104 @@
+use std::{
+    io::{self, IsTerminal},
+    os::fd::BorrowedFd,
+    time::{Duration, Instant},
+};
+
+use nix::{
+    errno::Errno,
+    poll::{self, PollFd, PollFlags, PollTimeout},
+    sys::termios::{self, LocalFlags, SetArg, SpecialCharacterIndices, Termios},
+    unistd,
+};
+
+use super::handle::Handle;
+
+pub struct ByteReader<'a> {
+    fd: Option<BorrowedFd<'a>>,
+    deadline: Option<Instant>,
+    modes: Option<Termios>,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(handle: &'a Handle, timeout: Option<Duration>) -> Self {
+        Self {
+            fd: handle.as_fd(),
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            modes: None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.fd.is_some_and(|fd| fd.is_terminal())
+    }
+
+    pub fn configure_terminal(&mut self, echo: bool, canonical: bool) -> io::Result<()> {
+        let Some(fd) = self.fd.filter(|fd| fd.is_terminal()) else {
+            return Ok(());
+        };
+
+        let modes = termios::tcgetattr(fd)?;
+        let mut changed = modes.clone();
+        changed.local_flags.set(LocalFlags::ECHO, echo);
+        changed.local_flags.set(LocalFlags::ICANON, canonical);
+        changed.control_chars[SpecialCharacterIndices::VMIN as usize] = 1;
+        changed.control_chars[SpecialCharacterIndices::VTIME as usize] = 0;
+
+        termios::tcsetattr(fd, SetArg::TCSANOW, &changed)?;
+        self.modes.get_or_insert(modes);
+        Ok(())
+    }
+
+    pub fn ready(&self) -> io::Result<bool> {
+        let Some(fd) = self.fd else {
+            return Ok(true);
+        };
+
+        let timeout = match self.deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                PollTimeout::try_from(remaining).unwrap_or(PollTimeout::MAX)
+            }
+            None => PollTimeout::NONE,
+        };
+
+        let mut fds = [PollFd::new(fd, PollFlags::POLLIN)];
+
+        loop {
+            match poll::poll(&mut fds, timeout) {
+                Ok(ready) => return Ok(ready > 0),
+                Err(Errno::EINTR) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    pub fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let Some(fd) = self.fd else {
+            return Ok(None);
+        };
+
+        if self.deadline.is_some() && !self.ready()? {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+
+        let mut buffer = [0u8; 1];
+
+        loop {
+            match unistd::read(fd, &mut buffer) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(buffer[0])),
+                Err(Errno::EINTR) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl Drop for ByteReader<'_> {
+    fn drop(&mut self) {
+        if let (Some(fd), Some(modes)) = (self.fd, self.modes.take()) {
+            _ = termios::tcsetattr(fd, SetArg::TCSANOW, &modes);
+        }
+    }
+}
@@ -1,2 +1,5 @@
-pub mod io;
 pub mod dir;
+pub mod io;
+pub mod process;
+pub mod resource;
+pub mod signal;
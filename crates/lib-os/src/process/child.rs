@@ -0,0 +1,109 @@
+use std::{io, os::unix::process::CommandExt, process::Command};
+
+use nix::{
+    sys::signal::{self, SigHandler, Signal},
+    unistd,
+};
+
+use super::{
+    Pid,
+    terminal::{self, JOB_CONTROL_SIGNALS, STOP_SIGNALS},
+};
+
+pub fn prepare_child(command: &mut Command, pgid: Option<Pid>, foreground: bool) {
+    let Some(terminal) = terminal::terminal() else {
+        return;
+    };
+
+    command.process_group(pgid.unwrap_or(0));
+
+    unsafe {
+        command.pre_exec(move || {
+            if foreground {
+                let pgid = pgid
+                    .map(unistd::Pid::from_raw)
+                    .unwrap_or_else(unistd::getpid);
+                _ = unistd::tcsetpgrp(terminal, pgid);
+            }
+
+            for sig in JOB_CONTROL_SIGNALS {
+                signal::signal(sig, SigHandler::SigDfl)?;
+            }
+
+            Ok(())
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fork {
+    Parent(Pid),
+    Child,
+}
+
+pub fn fork(pgid: Option<Pid>, foreground: bool) -> io::Result<Fork> {
+    let terminal = terminal::terminal();
+
+    match unsafe { unistd::fork() }? {
+        unistd::ForkResult::Parent { child } => {
+            if terminal.is_some() {
+                _ = unistd::setpgid(child, pgid.map(unistd::Pid::from_raw).unwrap_or(child));
+            }
+
+            Ok(Fork::Parent(child.as_raw()))
+        }
+        unistd::ForkResult::Child => {
+            crate::signal::reset_caught();
+
+            if let Some(terminal) = terminal {
+                let shell = pgid.is_some() && pgid == terminal::shell_group();
+                let pid = unistd::getpid();
+                let pgid = pgid.map(unistd::Pid::from_raw).unwrap_or(pid);
+                _ = unistd::setpgid(pid, pgid);
+
+                if foreground {
+                    _ = unistd::tcsetpgrp(terminal, pgid);
+                }
+
+                // Interrupts always reach the child. One staying in the shell's group keeps
+                // ignoring the stop signals, as a stop sent to the group must not suspend it
+                // behind the shell's back.
+                for sig in JOB_CONTROL_SIGNALS {
+                    if !(shell && STOP_SIGNALS.contains(&sig)) {
+                        unsafe { signal::signal(sig, SigHandler::SigDfl) }?;
+                    }
+                }
+
+                terminal::disable_job_control();
+            }
+
+            Ok(Fork::Child)
+        }
+    }
+}
+
+pub fn exec(command: &mut Command) -> io::Error {
+    unsafe {
+        command.pre_exec(|| {
+            for sig in JOB_CONTROL_SIGNALS {
+                signal::signal(sig, SigHandler::SigDfl)?;
+            }
+
+            Ok(())
+        });
+    }
+
+    command.exec()
+}
+
+pub fn resume(pgid: Pid) -> io::Result<()> {
+    Ok(signal::killpg(
+        unistd::Pid::from_raw(pgid),
+        Signal::SIGCONT,
+    )?)
+}
+
+pub fn hang_up(pgid: Pid) -> io::Result<()> {
+    signal::killpg(unistd::Pid::from_raw(pgid), Signal::SIGHUP)?;
+    resume(pgid)
+}
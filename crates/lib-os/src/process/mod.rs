@@ -0,0 +1,18 @@
+mod child;
+mod terminal;
+mod wait;
+
+pub use child::{Fork, exec, fork, hang_up, prepare_child, resume};
+pub use terminal::{
+    TerminalModes, disable_job_control, enable_job_control, give_terminal, job_control,
+    reclaim_terminal, shell_group,
+};
+pub use wait::{WaitStatus, wait};
+
+pub(crate) use terminal::JOB_CONTROL_SIGNALS;
+
+pub fn is_exec_format_error(error: &std::io::Error) -> bool {
+    error.raw_os_error() == Some(nix::errno::Errno::ENOEXEC as i32)
+}
+
+pub type Pid = i32;
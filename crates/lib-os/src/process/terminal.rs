@@ -0,0 +1,138 @@
+use std::{
+    io::{self, IsTerminal},
+    os::fd::{AsFd, BorrowedFd, OwnedFd},
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use nix::{
+    sys::{
+        signal::{self, SigHandler, Signal},
+        termios::{self, SetArg, Termios},
+    },
+    unistd,
+};
+
+use super::Pid;
+
+#[derive(Debug)]
+struct JobControl {
+    terminal: OwnedFd,
+    pgid: unistd::Pid,
+    modes: Mutex<Termios>,
+}
+
+static JOB_CONTROL: OnceLock<JobControl> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn state() -> Option<&'static JobControl> {
+    if ENABLED.load(Ordering::Relaxed) {
+        JOB_CONTROL.get()
+    } else {
+        None
+    }
+}
+
+pub(crate) const JOB_CONTROL_SIGNALS: [Signal; 5] = [
+    Signal::SIGINT,
+    Signal::SIGQUIT,
+    Signal::SIGTSTP,
+    Signal::SIGTTIN,
+    Signal::SIGTTOU,
+];
+
+/// The job control signals that stop a process rather than interrupt it.
+pub(crate) const STOP_SIGNALS: [Signal; 3] = [Signal::SIGTSTP, Signal::SIGTTIN, Signal::SIGTTOU];
+
+#[derive(Debug, Clone)]
+pub struct TerminalModes(Termios);
+
+pub fn enable_job_control() -> io::Result<()> {
+    if JOB_CONTROL.get().is_some() {
+        return Ok(());
+    }
+
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() {
+        return Err(io::Error::other("stdin is not a terminal"));
+    }
+
+    loop {
+        let pgrp = unistd::getpgrp();
+
+        if unistd::tcgetpgrp(stdin.as_fd())? == pgrp {
+            break;
+        }
+
+        signal::killpg(pgrp, Signal::SIGTTIN)?;
+    }
+
+    for sig in JOB_CONTROL_SIGNALS {
+        unsafe { signal::signal(sig, SigHandler::SigIgn) }?;
+    }
+
+    let pid = unistd::getpid();
+
+    if unistd::getpgrp() != pid {
+        unistd::setpgid(pid, pid)?;
+    }
+
+    unistd::tcsetpgrp(stdin.as_fd(), pid)?;
+
+    let job_control = JobControl {
+        terminal: stdin.as_fd().try_clone_to_owned()?,
+        pgid: pid,
+        modes: Mutex::new(termios::tcgetattr(stdin.as_fd())?),
+    };
+
+    _ = JOB_CONTROL.set(job_control);
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn disable_job_control() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn job_control() -> bool {
+    state().is_some()
+}
+
+/// The process group of the shell itself while job control is enabled.
+pub fn shell_group() -> Option<Pid> {
+    state().map(|jc| jc.pgid.as_raw())
+}
+
+pub(super) fn terminal() -> Option<BorrowedFd<'static>> {
+    state().map(|jc| jc.terminal.as_fd())
+}
+
+pub fn give_terminal(pgid: Pid, modes: Option<&TerminalModes>) -> io::Result<()> {
+    if let Some(jc) = state() {
+        if let Some(TerminalModes(modes)) = modes {
+            termios::tcsetattr(jc.terminal.as_fd(), SetArg::TCSADRAIN, modes)?;
+        }
+
+        unistd::tcsetpgrp(jc.terminal.as_fd(), unistd::Pid::from_raw(pgid))?;
+    }
+
+    Ok(())
+}
+
+pub fn reclaim_terminal() -> io::Result<Option<TerminalModes>> {
+    if let Some(jc) = state() {
+        unistd::tcsetpgrp(jc.terminal.as_fd(), jc.pgid)?;
+        let modes = termios::tcgetattr(jc.terminal.as_fd())?;
+
+        if let Ok(shell_modes) = jc.modes.lock() {
+            termios::tcsetattr(jc.terminal.as_fd(), SetArg::TCSADRAIN, &shell_modes)?;
+        }
+
+        Ok(Some(TerminalModes(modes)))
+    } else {
+        Ok(None)
+    }
+}
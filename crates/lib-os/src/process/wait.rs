@@ -0,0 +1,43 @@
+use std::io;
+
+use nix::{
+    errno::Errno,
+    sys::wait::{self, WaitPidFlag},
+    unistd,
+};
+
+use super::Pid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitStatus {
+    Exited(i32),
+    Signaled(i32),
+    Stopped(i32),
+    Continued,
+}
+
+impl WaitStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Exited(_) | Self::Signaled(_))
+    }
+}
+
+pub fn wait(pid: Pid, block: bool) -> io::Result<Option<WaitStatus>> {
+    let mut flags = WaitPidFlag::WUNTRACED | WaitPidFlag::WCONTINUED;
+
+    if !block {
+        flags |= WaitPidFlag::WNOHANG;
+    }
+
+    loop {
+        return match wait::waitpid(unistd::Pid::from_raw(pid), Some(flags)) {
+            Ok(wait::WaitStatus::Exited(_, code)) => Ok(Some(WaitStatus::Exited(code))),
+            Ok(wait::WaitStatus::Signaled(_, sig, _)) => Ok(Some(WaitStatus::Signaled(sig as i32))),
+            Ok(wait::WaitStatus::Stopped(_, sig)) => Ok(Some(WaitStatus::Stopped(sig as i32))),
+            Ok(wait::WaitStatus::Continued(_)) => Ok(Some(WaitStatus::Continued)),
+            Ok(_) => Ok(None),
+            Err(Errno::EINTR) => continue,
+            Err(e) => Err(e.into()),
+        };
+    }
+}
@@ -0,0 +1,49 @@
+use std::io;
+
+use nix::sys::resource::{self, RLIM_INFINITY};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    CoreSize,
+    DataSize,
+    FileSize,
+    OpenFiles,
+    StackSize,
+    CpuTime,
+    VirtualMemory,
+}
+
+impl Resource {
+    fn as_nix(self) -> resource::Resource {
+        match self {
+            Self::CoreSize => resource::Resource::RLIMIT_CORE,
+            Self::DataSize => resource::Resource::RLIMIT_DATA,
+            Self::FileSize => resource::Resource::RLIMIT_FSIZE,
+            Self::OpenFiles => resource::Resource::RLIMIT_NOFILE,
+            Self::StackSize => resource::Resource::RLIMIT_STACK,
+            Self::CpuTime => resource::Resource::RLIMIT_CPU,
+            Self::VirtualMemory => resource::Resource::RLIMIT_AS,
+        }
+    }
+}
+
+/// A soft or hard limit in the resource's own unit, where `None` is unlimited.
+pub type Limit = Option<u64>;
+
+/// Returns the soft and hard limits of `resource`.
+pub fn get_limit(resource: Resource) -> io::Result<(Limit, Limit)> {
+    let (soft, hard) = resource::getrlimit(resource.as_nix())?;
+    let limit = |value| (value != RLIM_INFINITY).then_some(value);
+
+    Ok((limit(soft), limit(hard)))
+}
+
+pub fn set_limit(resource: Resource, soft: Limit, hard: Limit) -> io::Result<()> {
+    resource::setrlimit(
+        resource.as_nix(),
+        soft.unwrap_or(RLIM_INFINITY),
+        hard.unwrap_or(RLIM_INFINITY),
+    )?;
+
+    Ok(())
+}
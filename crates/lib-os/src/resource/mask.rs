@@ -0,0 +1,16 @@
+use nix::sys::stat::{self, Mode};
+
+/// Returns the file mode creation mask. There is no way to read the mask without setting it, so it
+/// is briefly cleared and then restored.
+pub fn get_umask() -> u32 {
+    let mask = stat::umask(Mode::empty());
+    stat::umask(mask);
+
+    mask.bits() as u32
+}
+
+/// Sets the file mode creation mask, returning the previous one.
+pub fn set_umask(mask: u32) -> u32 {
+    let mode = Mode::from_bits_truncate((mask & 0o777) as _);
+    stat::umask(mode).bits() as u32
+}
@@ -0,0 +1,7 @@
+mod limit;
+mod mask;
+mod usage;
+
+pub use limit::{Limit, Resource, get_limit, set_limit};
+pub use mask::{get_umask, set_umask};
+pub use usage::{CpuTime, Who, cpu_time};
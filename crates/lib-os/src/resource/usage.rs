@@ -0,0 +1,35 @@
+use std::{io, time::Duration};
+
+use nix::sys::{
+    resource::{self, UsageWho},
+    time::{TimeVal, TimeValLike},
+};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Who {
+    Shell,
+    Children,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuTime {
+    pub user: Duration,
+    pub system: Duration,
+}
+
+fn duration(time: TimeVal) -> Duration {
+    Duration::from_micros(time.num_microseconds().max(0) as u64)
+}
+
+/// Returns the CPU time used by the shell, or by all of its children that have been waited for.
+pub fn cpu_time(who: Who) -> io::Result<CpuTime> {
+    let usage = resource::getrusage(match who {
+        Who::Shell => UsageWho::RUSAGE_SELF,
+        Who::Children => UsageWho::RUSAGE_CHILDREN,
+    })?;
+
+    Ok(CpuTime {
+        user: duration(usage.user_time()),
+        system: duration(usage.system_time()),
+    })
+}
@@ -0,0 +1,74 @@
+use std::{
+    io,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use nix::{
+    libc::c_int,
+    sys::signal::{self, SaFlags, SigAction, SigHandler, SigSet, Signal},
+};
+
+use crate::process::{self, JOB_CONTROL_SIGNALS};
+
+const NSIG: usize = 65;
+
+static PENDING: [AtomicBool; NSIG] = [const { AtomicBool::new(false) }; NSIG];
+static CAUGHT: [AtomicBool; NSIG] = [const { AtomicBool::new(false) }; NSIG];
+
+extern "C" fn record(sig: c_int) {
+    if let Some(flag) = PENDING.get(sig as usize) {
+        flag.store(true, Ordering::SeqCst);
+    }
+}
+
+fn set_handler(sig: i32, handler: SigHandler) -> io::Result<()> {
+    let signal = Signal::try_from(sig)?;
+    let action = SigAction::new(handler, SaFlags::SA_RESTART, SigSet::empty());
+    unsafe { signal::sigaction(signal, &action) }?;
+
+    if let Some(flag) = CAUGHT.get(sig as usize) {
+        flag.store(matches!(handler, SigHandler::Handler(_)), Ordering::SeqCst);
+    }
+
+    Ok(())
+}
+
+pub fn catch(sig: i32) -> io::Result<()> {
+    set_handler(sig, SigHandler::Handler(record))
+}
+
+pub fn ignore(sig: i32) -> io::Result<()> {
+    set_handler(sig, SigHandler::SigIgn)
+}
+
+pub fn restore(sig: i32) -> io::Result<()> {
+    let shell_ignores = process::job_control()
+        && JOB_CONTROL_SIGNALS
+            .iter()
+            .any(|signal| *signal as i32 == sig);
+
+    if shell_ignores {
+        set_handler(sig, SigHandler::SigIgn)
+    } else {
+        set_handler(sig, SigHandler::SigDfl)
+    }
+}
+
+pub fn pending() -> Vec<i32> {
+    PENDING
+        .iter()
+        .enumerate()
+        .filter(|(_, flag)| flag.swap(false, Ordering::SeqCst))
+        .map(|(sig, _)| sig as i32)
+        .collect()
+}
+
+pub(crate) fn reset_caught() {
+    for (sig, flag) in CAUGHT.iter().enumerate() {
+        if flag.load(Ordering::SeqCst) {
+            _ = set_handler(sig as i32, SigHandler::SigDfl);
+        }
+
+        PENDING[sig].store(false, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,9 @@
+mod handler;
+mod names;
+mod send;
+
+pub use handler::{catch, ignore, pending, restore};
+pub use names::{list, name, number};
+pub use send::send;
+
+pub(crate) use handler::reset_caught;
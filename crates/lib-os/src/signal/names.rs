@@ -0,0 +1,25 @@
+use nix::sys::signal::Signal;
+
+pub fn list() -> Vec<(i32, &'static str)> {
+    Signal::iterator()
+        .map(|sig| (sig as i32, &sig.as_str()[3..]))
+        .collect()
+}
+
+pub fn name(number: i32) -> Option<&'static str> {
+    Signal::try_from(number).ok().map(|sig| &sig.as_str()[3..])
+}
+
+pub fn number(spec: &str) -> Option<i32> {
+    if let Ok(number) = spec.parse::<i32>() {
+        return Signal::try_from(number).ok().map(|sig| sig as i32);
+    }
+
+    let spec = spec.to_uppercase();
+    let spec = spec.strip_prefix("SIG").unwrap_or(&spec);
+
+    list()
+        .into_iter()
+        .find(|(_, name)| *name == spec)
+        .map(|(number, _)| number)
+}
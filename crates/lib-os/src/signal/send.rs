@@ -0,0 +1,20 @@
+use std::io;
+
+use nix::{
+    sys::signal::{self, Signal},
+    unistd,
+};
+
+use crate::process::Pid;
+
+/// Sends `sig` to `pid`, or to the process group `-pid` if it is negative. Signal 0 only checks
+/// that the target exists.
+pub fn send(pid: Pid, sig: i32) -> io::Result<()> {
+    let signal = match sig {
+        0 => None,
+        _ => Some(Signal::try_from(sig)?),
+    };
+
+    signal::kill(unistd::Pid::from_raw(pid), signal)?;
+    Ok(())
+}
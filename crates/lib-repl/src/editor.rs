@@ -61,10 +61,8 @@ impl Editor {
                 (KeyModifiers::NONE, KeyCode::Left) => {
                     self.cursor = self.cursor.saturating_sub(1);
                 }
-                (KeyModifiers::NONE, KeyCode::Right) => {
-                    if self.cursor < self.buffer.len() {
-                        self.cursor += 1;
-                    }
+                (KeyModifiers::NONE, KeyCode::Right) if self.cursor < self.buffer.len() => {
+                    self.cursor += 1;
                 }
                 (KeyModifiers::NONE, KeyCode::Up) => {
                     if let Some(buffer) = self.history.back() {
@@ -78,16 +76,12 @@ impl Editor {
                         self.cursor = self.buffer.len();
                     }
                 }
-                (KeyModifiers::NONE, KeyCode::Backspace) => {
-                    if self.cursor > 0 {
-                        self.buffer.remove(self.cursor - 1);
-                        self.cursor -= 1;
-                    }
+                (KeyModifiers::NONE, KeyCode::Backspace) if self.cursor > 0 => {
+                    self.buffer.remove(self.cursor - 1);
+                    self.cursor -= 1;
                 }
-                (KeyModifiers::NONE, KeyCode::Delete) => {
-                    if self.cursor < self.buffer.len() {
-                        self.buffer.remove(self.cursor);
-                    }
+                (KeyModifiers::NONE, KeyCode::Delete) if self.cursor < self.buffer.len() => {
+                    self.buffer.remove(self.cursor);
                 }
                 (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(character)) => {
                     self.buffer.insert(self.cursor, character);
@@ -6,17 +6,13 @@ use std::{
 
 use itertools::Itertools;
 
+#[derive(Default)]
 pub enum Source {
+    #[default]
     None,
     File(PathBuf),
 }
 
-impl Default for Source {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl From<PathBuf> for Source {
     fn from(file_path: PathBuf) -> Self {
         _ = fs::create_dir_all(file_path.parent().unwrap());
@@ -6,9 +6,7 @@ use std::{
 use ansi_width::ansi_width;
 use crossterm::{cursor, event, queue, style, terminal};
 use itertools::Itertools;
-use sysexits::ExitCode;
-
-use lib_core::{Result, Shell};
+use lib_core::{ExitStatus, Result, Shell};
 
 use crate::{
     editor::{Editor, Signal},
@@ -192,32 +190,35 @@ impl<'a> Prompt<'a> {
         }
     }
 
-    pub fn repl(&mut self) -> Result<ExitCode> {
-        _ = ctrlc::set_handler(|| {});
+    pub fn repl(&mut self) -> Result<ExitStatus> {
+        self.shell.enable_job_control();
 
         while !self.shell.should_exit() {
+            self.shell.run_pending_traps();
+            self.shell.notify_jobs();
+
             match self.read_line() {
                 Ok(Signal::Buffer(buffer)) => {
                     self.shell.interpret(&buffer);
                 }
                 Ok(Signal::Interrupt) => {
                     writeln!(self.shell.stdout(), "^C")?;
-                    self.shell.set_exit_code(ExitCode::DataErr);
+                    self.shell.set_exit_code(ExitStatus::INTERRUPTED);
                     continue;
                 }
                 Ok(Signal::End) => {
                     writeln!(self.shell.stdout(), "^D")?;
-                    self.shell.set_exit_code(ExitCode::DataErr);
                     break;
                 }
                 Err(e) => {
                     writeln!(self.shell.stderr(), "crsh: error: {e:?}")?;
-                    self.shell.set_exit_code(ExitCode::DataErr);
+                    self.shell.set_exit_code(ExitStatus::FAILURE);
                 }
                 _ => {}
             }
         }
 
+        self.shell.hang_up_jobs();
         Ok(self.shell.exit_code())
     }
 }